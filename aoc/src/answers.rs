@@ -0,0 +1,104 @@
+//! Expected answers for the checked-in `input.txt` of every day.
+use crate::Part;
+use std::fmt;
+
+/// Answers of both parts, indexed by day - 1.
+pub const ANSWERS: [[&str; 2]; 17] = [
+    ["3295539", "4940441"],
+    ["4930687", "5335"],
+    ["446", "9006"],
+    ["1864", "1258"],
+    ["15508323", "9006327"],
+    ["147223", "340"],
+    ["43812", "59597414"],
+    ["1463", "GKCKH"],
+    ["3409270027", "82760"],
+    ["284", "404"],
+    ["2268", "CEPKZJCR"],
+    ["7471", "376243355967784"],
+    ["333", "16539"],
+    ["371695", "4052920"],
+    ["212", "358"],
+    ["68764632", "52825021"],
+    ["12512", "1409507"],
+];
+
+/// Returns the expected answer for a day (starting at 1) and part.
+pub fn expected(day: usize, part: Part) -> Option<&'static str> {
+    let answers = ANSWERS.get(day.checked_sub(1)?)?;
+    Some(match part {
+        Part::A => answers[0],
+        Part::B => answers[1],
+    })
+}
+
+/// Result of checking a solution against its expected answer.
+#[derive(Debug)]
+pub enum Verdict {
+    Ok,
+    /// The solution returned a different answer.
+    Wrong {
+        expected: &'static str,
+        actual: String,
+    },
+    /// The solution failed.
+    Failed(String),
+}
+
+impl Verdict {
+    pub fn is_ok(&self) -> bool {
+        matches!(self, Verdict::Ok)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Ok => write!(f, "ok"),
+            Verdict::Wrong { expected, actual } => {
+                if expected.contains('\n') || actual.contains('\n') {
+                    writeln!(f, "wrong answer")?;
+                    diff_lines(f, expected, actual)
+                } else {
+                    write!(f, "wrong answer: expected {}, got {}", expected, actual)
+                }
+            }
+            Verdict::Failed(e) => write!(f, "failed: {}", e),
+        }
+    }
+}
+
+/// Writes the lines of both answers, marking those that differ.
+fn diff_lines(f: &mut fmt::Formatter<'_>, expected: &str, actual: &str) -> fmt::Result {
+    let mut expected = expected.lines();
+    let mut actual = actual.lines();
+    loop {
+        match (expected.next(), actual.next()) {
+            (None, None) => return Ok(()),
+            (Some(e), Some(a)) if e == a => writeln!(f, "  {}", e)?,
+            (e, a) => {
+                if let Some(e) = e {
+                    writeln!(f, "- {}", e)?;
+                }
+                if let Some(a) = a {
+                    writeln!(f, "+ {}", a)?;
+                }
+            }
+        }
+    }
+}
+
+/// Runs a solution on `input` and compares its answer with the
+/// registry.
+pub fn verify(day: usize, part: Part, input: &str) -> Verdict {
+    let expected = match expected(day, part) {
+        Some(expected) => expected,
+        None => return Verdict::Failed(format!("no expected answer for day {}", day)),
+    };
+
+    match crate::run(day, part, input) {
+        Ok((actual, _)) if actual == expected => Verdict::Ok,
+        Ok((actual, _)) => Verdict::Wrong { expected, actual },
+        Err(e) => Verdict::Failed(e.to_string()),
+    }
+}
@@ -0,0 +1,246 @@
+//! Timing of the parsing and both parts of every day.
+//!
+//! Each step is run a few times to warm up, then timed over a number of
+//! iterations. Reports can be saved as JSON and compared with a previous
+//! run to flag regressions.
+use crate::{Result, SAMPLERS};
+use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
+use std::fmt;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Options {
+    /// Untimed runs before measuring.
+    pub warmup: usize,
+    /// Timed runs.
+    pub iterations: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            warmup: 3,
+            iterations: 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Step {
+    Parse,
+    A,
+    B,
+}
+
+impl Step {
+    pub const ALL: [Step; 3] = [Step::Parse, Step::A, Step::B];
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Step::Parse => "parse",
+            Step::A => "a",
+            Step::B => "b",
+        })
+    }
+}
+
+/// Distribution of the timings of a step, in nanoseconds.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stats {
+    pub iterations: usize,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub p90_ns: u64,
+    pub p99_ns: u64,
+    pub max_ns: u64,
+}
+
+impl Stats {
+    /// Computes the statistics of a non-empty set of samples.
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "no samples");
+
+        let mut ns: Vec<u64> = samples.iter().map(|d| d.as_nanos() as u64).collect();
+        ns.sort_unstable();
+
+        Self {
+            iterations: ns.len(),
+            min_ns: ns[0],
+            median_ns: percentile(&ns, 50),
+            p90_ns: percentile(&ns, 90),
+            p99_ns: percentile(&ns, 99),
+            max_ns: ns[ns.len() - 1],
+        }
+    }
+
+    pub fn median(&self) -> Duration {
+        Duration::from_nanos(self.median_ns)
+    }
+}
+
+/// Nearest-rank percentile of sorted values.
+fn percentile(sorted: &[u64], p: usize) -> u64 {
+    let rank = (p * sorted.len()).div_ceil(100);
+    sorted[rank.max(1) - 1]
+}
+
+/// Name of a step, as `day12 parse` or `day12b`.
+fn label(day: usize, step: Step) -> String {
+    match step {
+        Step::Parse => format!("day{} parse", day),
+        step => format!("day{}{}", day, step),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Measurement {
+    pub day: usize,
+    pub step: Step,
+    pub stats: Stats,
+}
+
+impl fmt::Display for Measurement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let d = Duration::from_nanos;
+        write!(
+            f,
+            "{:<12} median {:>10.2?}  p90 {:>10.2?}  p99 {:>10.2?}  min {:>10.2?}  max {:>10.2?}",
+            label(self.day, self.step),
+            d(self.stats.median_ns),
+            d(self.stats.p90_ns),
+            d(self.stats.p99_ns),
+            d(self.stats.min_ns),
+            d(self.stats.max_ns),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Report {
+    pub measurements: Vec<Measurement>,
+}
+
+impl Report {
+    pub fn get(&self, day: usize, step: Step) -> Option<&Measurement> {
+        self.measurements
+            .iter()
+            .find(|m| m.day == day && m.step == step)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, content + "\n")?;
+        Ok(())
+    }
+}
+
+/// Times one step of a day on `input`.
+pub fn measure(day: usize, step: Step, input: &str, options: &Options) -> Result<Measurement> {
+    let sample = SAMPLERS
+        .get(day.wrapping_sub(1))
+        .ok_or_else(|| format!("no solution for day {}", day))?;
+
+    Ok(Measurement {
+        day,
+        step,
+        stats: Stats::from_samples(&sample(input, step, options)?),
+    })
+}
+
+/// Timings of one step of a day, given its parser and the solutions of
+/// both parts. The parts are timed on an input parsed beforehand.
+pub fn sample<'a, P, T>(
+    input: &'a str,
+    step: Step,
+    options: &Options,
+    parse: fn(&'a str) -> Result<P>,
+    a: fn(&T) -> Result<String>,
+    b: fn(&T) -> Result<String>,
+) -> Result<Vec<Duration>>
+where
+    P: Borrow<T>,
+    T: ?Sized,
+{
+    let timed = |run: &dyn Fn() -> Result<()>| -> Result<Vec<Duration>> {
+        for _ in 0..options.warmup {
+            run()?;
+        }
+
+        let mut samples = Vec::with_capacity(options.iterations);
+        for _ in 0..options.iterations.max(1) {
+            let start = Instant::now();
+            run()?;
+            samples.push(start.elapsed());
+        }
+        Ok(samples)
+    };
+
+    let part = match step {
+        Step::Parse => return timed(&|| parse(input).map(drop)),
+        Step::A => a,
+        Step::B => b,
+    };
+    let parsed = parse(input)?;
+    timed(&|| part(parsed.borrow()).map(drop))
+}
+
+/// Change of the median time of a step between two reports.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub day: usize,
+    pub step: Step,
+    pub old: Duration,
+    pub new: Duration,
+}
+
+impl Comparison {
+    /// Relative change, e.g. `0.25` when 25% slower.
+    pub fn change(&self) -> f64 {
+        self.new.as_secs_f64() / self.old.as_secs_f64() - 1.0
+    }
+
+    /// Whether the step got slower by more than `threshold`.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change() > threshold
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:<12} {:.2?} -> {:.2?} ({:+.1}%)",
+            label(self.day, self.step),
+            self.old,
+            self.new,
+            self.change() * 100.0
+        )
+    }
+}
+
+/// Compares the steps measured in both reports.
+pub fn compare(baseline: &Report, current: &Report) -> Vec<Comparison> {
+    current
+        .measurements
+        .iter()
+        .filter_map(|m| {
+            let old = baseline.get(m.day, m.step)?;
+            Some(Comparison {
+                day: m.day,
+                step: m.step,
+                old: old.stats.median(),
+                new: m.stats.median(),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,210 @@
+use crate::bench::{Options, Step};
+use std::borrow::Borrow;
+use std::env;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+pub mod answers;
+pub mod bench;
+
+pub type Error = Box<dyn std::error::Error>;
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// A solution to one part of a day, taking the puzzle input and
+/// returning the answer.
+pub type Solution = fn(&str) -> Result<String>;
+
+/// Parses the input of a day, then solves a part from the parsed input.
+fn solve<'a, P, T>(
+    input: &'a str,
+    parse: fn(&'a str) -> Result<P>,
+    part: fn(&T) -> Result<String>,
+) -> Result<String>
+where
+    P: Borrow<T>,
+    T: ?Sized,
+{
+    part(parse(input)?.borrow())
+}
+
+/// Solutions of both parts of a day, each parsing the input.
+macro_rules! solutions {
+    ($day:ident, $a:ident, $b:ident) => {
+        [
+            |input| solve(input, $day::parse, $day::$a),
+            |input| solve(input, $day::parse, $day::$b),
+        ]
+    };
+}
+
+/// Solutions of both parts, indexed by day - 1.
+pub const DAYS: [[Solution; 2]; 17] = [
+    solutions!(day1, day1a, day1b),
+    solutions!(day2, day2a, day2b),
+    solutions!(day3, day3a, day3b),
+    solutions!(day4, day4a, day4b),
+    solutions!(day5, day5a, day5b),
+    solutions!(day6, day6a, day6b),
+    solutions!(day7, day7a, day7b),
+    solutions!(day8, day8a, day8b),
+    solutions!(day9, day9a, day9b),
+    solutions!(day10, day10a, day10b),
+    solutions!(day11, day11a, day11b),
+    solutions!(day12, day12a, day12b),
+    solutions!(day13, day13a, day13b),
+    solutions!(day14, day14a, day14b),
+    solutions!(day15, day15a, day15b),
+    solutions!(day16, day16a, day16b),
+    solutions!(day17, day17a, day17b),
+];
+
+/// Times a step of a day on an input, see `bench::sample`.
+pub type Sampler = fn(&str, Step, &Options) -> Result<Vec<Duration>>;
+
+/// Timing of the steps of a day, the parts being given the parsed input.
+macro_rules! sampler {
+    ($day:ident, $a:ident, $b:ident) => {
+        |input, step, options| bench::sample(input, step, options, $day::parse, $day::$a, $day::$b)
+    };
+}
+
+/// Step timers, indexed by day - 1.
+pub const SAMPLERS: [Sampler; 17] = [
+    sampler!(day1, day1a, day1b),
+    sampler!(day2, day2a, day2b),
+    sampler!(day3, day3a, day3b),
+    sampler!(day4, day4a, day4b),
+    sampler!(day5, day5a, day5b),
+    sampler!(day6, day6a, day6b),
+    sampler!(day7, day7a, day7b),
+    sampler!(day8, day8a, day8b),
+    sampler!(day9, day9a, day9b),
+    sampler!(day10, day10a, day10b),
+    sampler!(day11, day11a, day11b),
+    sampler!(day12, day12a, day12b),
+    sampler!(day13, day13a, day13b),
+    sampler!(day14, day14a, day14b),
+    sampler!(day15, day15a, day15b),
+    sampler!(day16, day16a, day16b),
+    sampler!(day17, day17a, day17b),
+];
+
+/// Draws a picture of a day, from its input.
+pub type Picture = fn(&str) -> Result<export::Image>;
+
+/// Days having a picture to export.
+pub const PICTURES: [(usize, Picture); 5] = [
+    (8, day8::image),
+    (11, day11::image),
+    (13, day13::image),
+    (15, day15::image),
+    (17, day17::image),
+];
+
+/// Returns the picture of a day, if it has one.
+pub fn picture(day: usize) -> Option<Picture> {
+    PICTURES
+        .iter()
+        .find(|(d, _)| *d == day)
+        .map(|(_, picture)| *picture)
+}
+
+/// Records the simulation of a day, from its input.
+pub type Replay = fn(&str, &mut export::replay::Recorder) -> Result<()>;
+
+/// Days having a simulation to record.
+pub const REPLAYS: [(usize, Replay); 4] = [
+    (11, day11::replay),
+    (12, day12::replay),
+    (13, day13::replay),
+    (15, day15::replay),
+];
+
+/// Returns the simulation of a day, if it has one.
+pub fn replay(day: usize) -> Option<Replay> {
+    REPLAYS
+        .iter()
+        .find(|(d, _)| *d == day)
+        .map(|(_, replay)| *replay)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    A,
+    B,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::A, Part::B];
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Part::A => "a",
+            Part::B => "b",
+        })
+    }
+}
+
+impl std::str::FromStr for Part {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "a" | "A" | "1" => Ok(Part::A),
+            "b" | "B" | "2" => Ok(Part::B),
+            _ => Err(format!("invalid part {:?}, expected a or b", s).into()),
+        }
+    }
+}
+
+/// Returns the solution for a day (starting at 1) and part.
+pub fn solution(day: usize, part: Part) -> Option<Solution> {
+    let parts = DAYS.get(day.checked_sub(1)?)?;
+    Some(match part {
+        Part::A => parts[0],
+        Part::B => parts[1],
+    })
+}
+
+/// Locates the `input.txt` of a day.
+///
+/// The `dayN` directories are searched from the current directory
+/// upwards, so that the runner works from anywhere in the repository,
+/// before falling back to the repository the binary was built from.
+pub fn find_input(day: usize) -> Option<PathBuf> {
+    let rel = Path::new(&format!("day{}", day)).join("input.txt");
+
+    if let Ok(cwd) = env::current_dir() {
+        for dir in cwd.ancestors() {
+            let path = dir.join(&rel);
+            if path.is_file() {
+                return Some(path);
+            }
+        }
+    }
+
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join(&rel);
+    if path.is_file() {
+        Some(path)
+    } else {
+        None
+    }
+}
+
+/// Reads the input of a day, see [`find_input`].
+pub fn read_input(day: usize) -> Result<String> {
+    let path = find_input(day).ok_or_else(|| format!("cannot find day{}/input.txt", day))?;
+    Ok(std::fs::read_to_string(path)?)
+}
+
+/// Runs a solution, returning its answer along with the time it took.
+pub fn run(day: usize, part: Part, input: &str) -> Result<(String, Duration)> {
+    let solve = solution(day, part).ok_or_else(|| format!("no solution for day {}", day))?;
+
+    let start = Instant::now();
+    let answer = solve(input)?;
+    Ok((answer, start.elapsed()))
+}
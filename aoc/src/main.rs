@@ -0,0 +1,322 @@
+use aoc::bench::{self, Options, Report, Step};
+use aoc::{answers, Part, Result, DAYS};
+use day12::series;
+use export::replay::{self, Recorder};
+use std::env;
+use std::time::Duration;
+
+const USAGE: &str = "usage:
+    aoc run <day> [a|b] [--input <path>]
+    aoc all
+    aoc verify [day]
+    aoc image <day> <path> [--scale <n>] [--input <path>]
+    aoc replay <day> <path> [--every <n>] [--max-frames <n>] [--scale <n>]
+               [--delay <ms>] [--input <path>]
+    aoc energy <path> [--steps <n>] [--every <n>] [--input <path>]
+    aoc bench [day] [--warmup <n>] [--iterations <n>] [--save <path>]
+              [--baseline <path>] [--threshold <percent>]";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    if let Err(e) = run_command(&args) {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run_command(args: &[String]) -> Result<()> {
+    match args.first().map(String::as_str) {
+        Some("run") => run_day(&args[1..]),
+        Some("all") if args.len() == 1 => run_all(),
+        Some("verify") if args.len() <= 2 => verify(args.get(1)),
+        Some("bench") => run_bench(&args[1..]),
+        Some("image") => export_image(&args[1..]),
+        Some("replay") => record_replay(&args[1..]),
+        Some("energy") => export_energy(&args[1..]),
+        _ => Err(USAGE.into()),
+    }
+}
+
+fn run_day(args: &[String]) -> Result<()> {
+    let mut day = None;
+    let mut parts = None;
+    let mut input_path = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--input" {
+            input_path = Some(args.next().ok_or("missing path after --input")?);
+        } else if day.is_none() {
+            day = Some(parse_day(arg)?);
+        } else if parts.is_none() {
+            parts = Some(vec![arg.parse::<Part>()?]);
+        } else {
+            return Err(USAGE.into());
+        }
+    }
+
+    let day = day.ok_or(USAGE)?;
+    let input = match input_path {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => aoc::read_input(day)?,
+    };
+
+    for part in parts.unwrap_or_else(|| Part::ALL.to_vec()) {
+        let (answer, elapsed) = aoc::run(day, part, &input)?;
+        print_answer(day, part, &answer, elapsed);
+    }
+    Ok(())
+}
+
+fn run_all() -> Result<()> {
+    let mut total = Duration::default();
+
+    for day in 1..=DAYS.len() {
+        let input = aoc::read_input(day)?;
+        for part in Part::ALL.iter() {
+            let (answer, elapsed) = aoc::run(day, *part, &input)?;
+            print_answer(day, *part, &answer, elapsed);
+            total += elapsed;
+        }
+    }
+    println!("total: {:.2?}", total);
+    Ok(())
+}
+
+fn verify(day: Option<&String>) -> Result<()> {
+    let days = match day {
+        Some(day) => {
+            let day = parse_day(day)?;
+            day..=day
+        }
+        None => 1..=DAYS.len(),
+    };
+
+    let mut nb_failed = 0;
+    for day in days {
+        let input = aoc::read_input(day)?;
+        for part in Part::ALL.iter() {
+            let verdict = answers::verify(day, *part, &input);
+            println!("day{}{}: {}", day, part, verdict);
+            if !verdict.is_ok() {
+                nb_failed += 1;
+            }
+        }
+    }
+
+    if nb_failed > 0 {
+        Err(format!("{} answer(s) do not match", nb_failed).into())
+    } else {
+        Ok(())
+    }
+}
+
+fn run_bench(args: &[String]) -> Result<()> {
+    let mut options = Options::default();
+    let mut day = None;
+    let mut save = None;
+    let mut baseline = None;
+    let mut threshold = 10.0;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("missing value after {}", arg))
+        };
+        match arg.as_str() {
+            "--warmup" => options.warmup = value()?.parse()?,
+            "--iterations" => options.iterations = value()?.parse()?,
+            "--save" => save = Some(value()?),
+            "--baseline" => baseline = Some(Report::load(value()?)?),
+            "--threshold" => threshold = value()?.parse()?,
+            _ if day.is_none() => day = Some(parse_day(arg)?),
+            _ => return Err(USAGE.into()),
+        }
+    }
+
+    let days = match day {
+        Some(day) => day..=day,
+        None => 1..=DAYS.len(),
+    };
+
+    let mut report = Report::default();
+    for day in days {
+        let input = aoc::read_input(day)?;
+        for step in Step::ALL.iter() {
+            let measurement = bench::measure(day, *step, &input, &options)?;
+            println!("{}", measurement);
+            report.measurements.push(measurement);
+        }
+    }
+
+    if let Some(path) = save {
+        report.save(path)?;
+    }
+
+    if let Some(baseline) = baseline {
+        let mut nb_regressions = 0;
+        println!();
+        for comparison in bench::compare(&baseline, &report) {
+            let regressed = comparison.is_regression(threshold / 100.0);
+            println!(
+                "{}{}",
+                comparison,
+                if regressed { "  REGRESSION" } else { "" }
+            );
+            if regressed {
+                nb_regressions += 1;
+            }
+        }
+        if nb_regressions > 0 {
+            return Err(format!(
+                "{} step(s) slower than the baseline by more than {}%",
+                nb_regressions, threshold
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
+
+fn export_image(args: &[String]) -> Result<()> {
+    let mut day = None;
+    let mut path = None;
+    let mut scale = 8;
+    let mut input_path = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("missing value after {}", arg))
+        };
+        match arg.as_str() {
+            "--scale" => scale = value()?.parse()?,
+            "--input" => input_path = Some(value()?),
+            _ if day.is_none() => day = Some(parse_day(arg)?),
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err(USAGE.into()),
+        }
+    }
+
+    let (day, path) = match (day, path) {
+        (Some(day), Some(path)) => (day, path),
+        _ => return Err(USAGE.into()),
+    };
+    let picture = aoc::picture(day).ok_or_else(|| format!("no picture for day {}", day))?;
+    let input = match input_path {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => aoc::read_input(day)?,
+    };
+
+    let image = picture(&input)?;
+    image.save(path, scale)?;
+    println!(
+        "day{}: {}x{} image saved to {}",
+        day,
+        image.width(),
+        image.height(),
+        path
+    );
+    Ok(())
+}
+
+fn record_replay(args: &[String]) -> Result<()> {
+    let mut options = replay::Options::default();
+    let mut day = None;
+    let mut path = None;
+    let mut scale = 4;
+    let mut delay = Duration::from_millis(40);
+    let mut input_path = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("missing value after {}", arg))
+        };
+        match arg.as_str() {
+            "--every" => options.every = value()?.parse()?,
+            "--max-frames" => options.max_frames = Some(value()?.parse()?),
+            "--scale" => scale = value()?.parse()?,
+            "--delay" => delay = Duration::from_millis(value()?.parse()?),
+            "--input" => input_path = Some(value()?),
+            _ if day.is_none() => day = Some(parse_day(arg)?),
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err(USAGE.into()),
+        }
+    }
+
+    let (day, path) = match (day, path) {
+        (Some(day), Some(path)) => (day, path),
+        _ => return Err(USAGE.into()),
+    };
+    let simulation = aoc::replay(day).ok_or_else(|| format!("no replay for day {}", day))?;
+    let input = match input_path {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => aoc::read_input(day)?,
+    };
+
+    let mut recorder = Recorder::new(options);
+    simulation(&input, &mut recorder)?;
+    let paths = recorder.save(path, scale, delay)?;
+    println!(
+        "day{}: {} frame(s) saved to {} file(s)",
+        day,
+        recorder.frames().len(),
+        paths.len()
+    );
+    Ok(())
+}
+
+/// Saves the energies and trajectories of the moons of day 12.
+fn export_energy(args: &[String]) -> Result<()> {
+    let mut options = series::Options::default();
+    let mut path = None;
+    let mut input_path = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("missing value after {}", arg))
+        };
+        match arg.as_str() {
+            "--steps" => options.steps = value()?.parse()?,
+            "--every" => options.every = value()?.parse()?,
+            "--input" => input_path = Some(value()?),
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err(USAGE.into()),
+        }
+    }
+
+    let path = path.ok_or(USAGE)?;
+    let input = match input_path {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => aoc::read_input(12)?,
+    };
+
+    let system = day12::parse(&input)?;
+    let count = series::save(&system, options, path)?;
+    println!("day12: {} sample(s) saved to {}", count, path);
+    Ok(())
+}
+
+fn parse_day(arg: &str) -> Result<usize> {
+    match arg.parse::<usize>() {
+        Ok(day) if day >= 1 && day <= DAYS.len() => Ok(day),
+        Ok(day) => Err(format!("no solution for day {}", day).into()),
+        Err(_) => Err(format!("invalid day {:?}", arg).into()),
+    }
+}
+
+fn print_answer(day: usize, part: Part, answer: &str, elapsed: Duration) {
+    if answer.contains('\n') {
+        println!("day{}{} ({:.2?}):", day, part, elapsed);
+        println!("{}", answer);
+    } else {
+        println!("day{}{}: {} ({:.2?})", day, part, answer, elapsed);
+    }
+}
@@ -0,0 +1,71 @@
+use aoc::answers::{self, Verdict};
+use aoc::Part;
+
+fn check(day: usize) {
+    let input = aoc::read_input(day).unwrap();
+    for part in Part::ALL.iter() {
+        match answers::verify(day, *part, &input) {
+            Verdict::Ok => (),
+            verdict => panic!("day{}{}: {}", day, part, verdict),
+        }
+    }
+}
+
+macro_rules! days {
+    ($($name:ident: $day:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                check($day);
+            }
+        )*
+    };
+}
+
+days! {
+    day1: 1,
+    day2: 2,
+    day3: 3,
+    day4: 4,
+    day5: 5,
+    day6: 6,
+    day7: 7,
+    day8: 8,
+    day9: 9,
+    day10: 10,
+    day11: 11,
+    day12: 12,
+    day13: 13,
+    day14: 14,
+    day15: 15,
+    day16: 16,
+    day17: 17,
+}
+
+#[test]
+fn registry_covers_every_day() {
+    assert_eq!(answers::ANSWERS.len(), aoc::DAYS.len());
+    assert!(answers::expected(0, Part::A).is_none());
+    assert!(answers::expected(aoc::DAYS.len() + 1, Part::A).is_none());
+}
+
+#[test]
+fn wrong_answer_is_reported() {
+    // day 1 on a single module of mass 12 needs 2 fuel, not the
+    // registered answer
+    match answers::verify(1, Part::A, "12\n") {
+        Verdict::Wrong { expected, actual } => {
+            assert_eq!(expected, "3295539");
+            assert_eq!(actual, "2");
+        }
+        verdict => panic!("unexpected verdict {:?}", verdict),
+    }
+}
+
+#[test]
+fn failing_solution_is_reported() {
+    assert!(matches!(
+        answers::verify(1, Part::A, "not a mass\n"),
+        Verdict::Failed(_)
+    ));
+}
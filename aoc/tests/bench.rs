@@ -0,0 +1,88 @@
+use aoc::bench::{self, Measurement, Options, Report, Stats, Step};
+use std::time::Duration;
+
+fn stats(median_ms: u64) -> Stats {
+    Stats::from_samples(&[Duration::from_millis(median_ms)])
+}
+
+#[test]
+fn percentiles() {
+    let samples: Vec<_> = (1..=100).rev().map(Duration::from_nanos).collect();
+    let stats = Stats::from_samples(&samples);
+
+    assert_eq!(stats.iterations, 100);
+    assert_eq!(stats.min_ns, 1);
+    assert_eq!(stats.median_ns, 50);
+    assert_eq!(stats.p90_ns, 90);
+    assert_eq!(stats.p99_ns, 99);
+    assert_eq!(stats.max_ns, 100);
+
+    let stats = Stats::from_samples(&[Duration::from_nanos(7)]);
+    assert_eq!(stats.median_ns, 7);
+    assert_eq!(stats.p99_ns, 7);
+}
+
+#[test]
+fn measure_steps() {
+    let options = Options {
+        warmup: 1,
+        iterations: 3,
+    };
+    for step in Step::ALL.iter() {
+        let m = bench::measure(1, *step, "12\n14\n", &options).unwrap();
+        assert_eq!(m.day, 1);
+        assert_eq!(m.step, *step);
+        assert_eq!(m.stats.iterations, 3);
+    }
+
+    assert!(bench::measure(1, Step::Parse, "twelve\n", &options).is_err());
+}
+
+#[test]
+fn compare_reports() {
+    let report = |a, b| Report {
+        measurements: vec![
+            Measurement {
+                day: 12,
+                step: Step::A,
+                stats: stats(a),
+            },
+            Measurement {
+                day: 12,
+                step: Step::B,
+                stats: stats(b),
+            },
+        ],
+    };
+    let mut current = report(10, 130);
+    current.measurements.push(Measurement {
+        day: 16,
+        step: Step::B,
+        stats: stats(50),
+    });
+
+    let comparisons = bench::compare(&report(10, 100), &current);
+    // day16b is not in the baseline
+    assert_eq!(comparisons.len(), 2);
+    assert!(!comparisons[0].is_regression(0.1));
+    assert!(comparisons[1].is_regression(0.1));
+    assert!(!comparisons[1].is_regression(0.5));
+    assert!((comparisons[1].change() - 0.3).abs() < 1e-9);
+}
+
+#[test]
+fn save_and_load() {
+    let report = Report {
+        measurements: vec![Measurement {
+            day: 14,
+            step: Step::Parse,
+            stats: stats(3),
+        }],
+    };
+    let path = std::env::temp_dir().join(format!("aoc-bench-{}.json", std::process::id()));
+
+    report.save(&path).unwrap();
+    let loaded = Report::load(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.unwrap(), report);
+}
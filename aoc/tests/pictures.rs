@@ -0,0 +1,43 @@
+use grid::Pos;
+
+#[test]
+fn draw_pictures() {
+    let sizes = [
+        (8, (25, 6)),
+        (11, (43, 6)),
+        (13, (44, 20)),
+        (15, (41, 41)),
+        (17, (53, 65)),
+    ];
+    for (day, size) in sizes.iter() {
+        let picture = aoc::picture(*day).unwrap();
+        let image = picture(&aoc::read_input(*day).unwrap()).unwrap();
+        assert_eq!((image.width(), image.height()), *size, "day {}", day);
+    }
+    assert!(aoc::picture(1).is_none());
+
+    // the droid starts in an open space of the maze
+    let image = aoc::picture(15).unwrap()(&aoc::read_input(15).unwrap()).unwrap();
+    assert_eq!(image.get(Pos::new(21, 21)), Some(export::Rgb::WHITE));
+}
+
+#[test]
+fn record_replays() {
+    use export::replay::{Options, Recorder};
+
+    for (day, _) in aoc::REPLAYS.iter() {
+        let options = Options {
+            every: 3,
+            max_frames: Some(4),
+        };
+        let mut recorder = Recorder::new(options);
+        let replay = aoc::replay(*day).unwrap();
+        replay(&aoc::read_input(*day).unwrap(), &mut recorder).unwrap();
+
+        let frames = recorder.frames();
+        assert_eq!(frames.len(), 4, "day {}", day);
+        assert!(frames.windows(2).all(|w| w[0].width() == w[1].width()));
+        assert!(frames.windows(2).any(|w| w[0] != w[1]), "day {}", day);
+    }
+    assert!(aoc::replay(8).is_none());
+}
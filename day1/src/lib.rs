@@ -1,17 +1,5 @@
-use std::io;
-use std::io::Read;
-
 type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 
-fn main() -> Result<()> {
-    let mut input = String::new();
-    io::stdin().read_to_string(&mut input)?;
-
-    day1a(&input)?;
-    day1b(&input)?;
-    Ok(())
-}
-
 fn compute_fuel_weight(weight: i32) -> i32 {
     weight / 3 - 2
 }
@@ -31,28 +19,28 @@ fn compute_total_weight(mut weight: i32) -> i32 {
     acc
 }
 
-fn day1a(input: &str) -> Result<()> {
+pub fn parse(input: &str) -> Result<Vec<i32>> {
+    Ok(puzzle::one_per_line(input)?)
+}
+
+pub fn day1a(modules: &[i32]) -> Result<String> {
     let mut total = 0;
 
-    for line in input.lines() {
-        let module = line.parse::<i32>()?;
+    for &module in modules {
         let fuel = compute_fuel_weight(module);
 
         total += fuel;
     }
-    println!("day1a total weight: {}", total);
-    Ok(())
+    Ok(total.to_string())
 }
 
-fn day1b(input: &str) -> Result<()> {
+pub fn day1b(modules: &[i32]) -> Result<String> {
     let mut total = 0;
 
-    for line in input.lines() {
-        let module = line.parse::<i32>()?;
+    for &module in modules {
         let fuel = compute_total_weight(module);
 
         total += fuel;
     }
-    println!("day1b total weight: {}", total);
-    Ok(())
+    Ok(total.to_string())
 }
\ No newline at end of file
@@ -1,64 +1,39 @@
-use std::io;
-use std::io::Read;
+use grid::{Grid, Pos};
 
 type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 
-#[derive(Debug, Clone, PartialEq)]
-struct Pos {
-    x: i32,
-    y: i32,
+pub struct Map {
+    map: Grid<bool>,
+    asteroids: Vec<Pos>,
 }
 
-fn main() -> Result<()> {
-    let mut input = String::new();
-    io::stdin().read_to_string(&mut input)?;
-
-    let mut map: Vec<Vec<bool>> = Vec::new();
-    for line in input.lines() {
-        map.push(line.chars().map(|c| c == '#').collect());
-    }
-
-    let mut asteroids = Vec::new();
-    for (y, line) in map.iter().enumerate() {
-        for (x, is_asteroid) in line.iter().enumerate() {
-            if *is_asteroid {
-                asteroids.push(Pos { x: x as i32, y: y as i32 });
-            }
-        }
-    }
-
-    let base = find_base(&map, &asteroids);
-    day10b(&map, &asteroids, &base);
-    Ok(())
+pub fn parse(input: &str) -> Result<Map> {
+    let map = Grid::parse(input, |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })?;
+    let asteroids = map
+        .iter()
+        .filter(|(_, is_asteroid)| **is_asteroid)
+        .map(|(pos, _)| pos)
+        .collect();
+
+    Ok(Map { map, asteroids })
 }
 
-fn get_movement_vector(a: &Pos, b: &Pos) -> (i32, i32) {
+fn get_movement_vector(a: &Pos, b: &Pos) -> (i64, i64) {
     (b.x - a.x, b.y - a.y)
 }
 
-fn gcd(mut a: i32, mut b: i32) -> i32 {
-    if a == 0 {
-        b.abs()
-    } else if b == 0 {
-        a.abs()
-    } else {
-        while a != 0 {
-            let t = a;
-            a = b % a;
-            b = t;
-        }
-        b.abs()
-    }
-}
-
-fn normalize_vector(vec: &mut (i32, i32)) {
-    let c = gcd(vec.0, vec.1);
+fn normalize_vector(vec: &mut (i64, i64)) {
+    let c = math::gcd(vec.0, vec.1);
     vec.0 /= c;
     vec.1 /= c;
 }
 
-fn get_vision_rank(map: &Vec<Vec<bool>>, from: &Pos, to: &Pos, mut vec: (i32, i32)) -> u32 {
-   let mut at = from.clone();
+fn get_vision_rank(map: &Grid<bool>, from: &Pos, to: &Pos, mut vec: (i64, i64)) -> u32 {
+   let mut at = *from;
    let mut rank = 1;
 
    normalize_vector(&mut vec);
@@ -67,17 +42,18 @@ fn get_vision_rank(map: &Vec<Vec<bool>>, from: &Pos, to: &Pos, mut vec: (i32, i3
        at.y += vec.1;
        if at == *to {
            return rank;
-       } else if map[at.y as usize][at.x as usize] {
+       } else if map[at] {
            rank += 1;
        }
    }
 }
 
-fn is_visible(map: &Vec<Vec<bool>>, from: &Pos, to: &Pos, vec: (i32, i32)) -> bool {
+fn is_visible(map: &Grid<bool>, from: &Pos, to: &Pos, vec: (i64, i64)) -> bool {
     get_vision_rank(map, from, to, vec) == 1
 }
 
-fn find_base(map: &Vec<Vec<bool>>, asteroids: &Vec<Pos>) -> Pos {
+/// Find the asteroid seeing the most other asteroids, and their number.
+fn find_base(map: &Grid<bool>, asteroids: &[Pos]) -> Option<(Pos, u32)> {
     let mut max_visible = 0;
     let mut best_base = None;
 
@@ -102,13 +78,17 @@ fn find_base(map: &Vec<Vec<bool>>, asteroids: &Vec<Pos>) -> Pos {
 
         if visible > max_visible {
             max_visible = visible;
-            best_base = Some(base.clone());
+            best_base = Some(*base);
         }
     }
 
-    // for every asteroid, compute vector
-    println!("day10a maximum number of visible asteroids: {:?}", max_visible);
-    best_base.unwrap()
+    best_base.map(|base| (base, max_visible))
+}
+
+pub fn day10a(Map { map, asteroids }: &Map) -> Result<String> {
+    let (_, max_visible) = find_base(map, asteroids).ok_or("no asteroids")?;
+
+    Ok(max_visible.to_string())
 }
 
 #[derive(Debug, Ord, Eq, PartialOrd, PartialEq)]
@@ -118,8 +98,9 @@ struct PolarVecToAsteroid {
     asteroid_pos: usize,
 }
 
-fn day10b(map: &Vec<Vec<bool>>, asteroids: &Vec<Pos>, base: &Pos)
-{
+pub fn day10b(Map { map, asteroids }: &Map) -> Result<String> {
+    let (base, _) = find_base(map, asteroids).ok_or("no asteroids")?;
+    let base = &base;
     let mut vecs = Vec::new();
 
     for (pos, asteroid) in asteroids.iter().enumerate() {
@@ -150,6 +131,6 @@ fn day10b(map: &Vec<Vec<bool>>, asteroids: &Vec<Pos>, base: &Pos)
     // sort vecs according to (theta, r2)
     vecs.sort();
 
-    let selected = &asteroids[vecs[199].asteroid_pos];
-    println!("day10b 200th asteroid is: {}", selected.x * 100 + selected.y);
+    let selected = &asteroids[vecs.get(199).ok_or("less than 200 asteroids")?.asteroid_pos];
+    Ok((selected.x * 100 + selected.y).to_string())
 }
\ No newline at end of file
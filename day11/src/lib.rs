@@ -0,0 +1,151 @@
+use export::replay::Recorder;
+use export::{Image, Rgb};
+use grid::Grid;
+use intcode::Intcode;
+use std::collections::HashMap;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+pub fn parse(input: &str) -> Result<Vec<i64>> {
+    Ok(puzzle::comma_separated(input)?)
+}
+
+/// Runs the robot, calling `on_paint` with the panel painted at every
+/// step and the position the robot moved to.
+fn run_robot<F>(state: &[i64], init_color: i64, mut on_paint: F) -> HashMap<(i32, i32), i64>
+where
+    F: FnMut((i32, i32), i64, (i32, i32)),
+{
+    let mut robot = Intcode::new(state);
+    let mut painted = HashMap::new();
+    let mut pos = (0, 0);
+    const DIRECTIONS: [(i32, i32); 4] = [(0, 1), (-1, 0), (0, -1), (1, 0)];
+    let mut dir_pos = 1;
+
+    painted.insert(pos, init_color);
+    loop {
+        // 1: white, 0: black
+        let color = painted.get(&pos).unwrap_or(&0);
+        let outputs = robot.run(&[*color]);
+
+        painted.insert(pos, outputs[0]);
+        let panel = pos;
+        if outputs[1] == 0 {
+            dir_pos = (dir_pos + 1) % 4;
+        } else {
+            dir_pos = (dir_pos + 3) % 4;
+        }
+        pos.0 += DIRECTIONS[dir_pos].0;
+        pos.1 += DIRECTIONS[dir_pos].1;
+        on_paint(panel, outputs[0], pos);
+
+        if robot.is_done() {
+            break;
+        }
+    }
+
+    painted
+}
+
+pub fn day11a(state: &[i64]) -> Result<String> {
+    let painted = run_robot(state, 0, |_, _, _| ());
+
+    Ok(painted.len().to_string())
+}
+
+struct Pos {
+    x: i32,
+    y: i32,
+}
+
+struct Bounds {
+    min: Pos,
+    max: Pos,
+}
+
+fn get_bounds(painted: &HashMap<(i32, i32), i64>) -> Bounds {
+    let mut bounds = Bounds {
+        min: Pos { x: 0, y: 0 },
+        max: Pos { x: 0, y: 0 },
+    };
+
+    for c in painted.keys() {
+        bounds.min.x = std::cmp::min(bounds.min.x, c.0);
+        bounds.max.x = std::cmp::max(bounds.max.x, c.0);
+        bounds.min.y = std::cmp::min(bounds.min.y, c.1);
+        bounds.max.y = std::cmp::max(bounds.max.y, c.1);
+    }
+    bounds
+}
+
+/// The painted panels, white ones being lit.
+fn hull(painted: &HashMap<(i32, i32), i64>) -> Grid<bool> {
+    let bounds = get_bounds(painted);
+    // rows go along x, as the robot paints the letters sideways
+    let mut hull = Grid::new(
+        (bounds.max.y - bounds.min.y + 1) as usize,
+        (bounds.max.x - bounds.min.x + 1) as usize,
+        false,
+    );
+    for (&(x, y), color) in painted.iter() {
+        let pos = grid::Pos::new((y - bounds.min.y) as i64, (x - bounds.min.x) as i64);
+        hull[pos] = *color == 1;
+    }
+    hull
+}
+
+pub fn day11b(state: &[i64]) -> Result<String> {
+    let message = hull(&run_robot(state, 1, |_, _, _| ()));
+    ocr::recognize(&message).map_err(|e| {
+        let art = message.render(|lit| if *lit { 'X' } else { ' ' });
+        format!("{}\n{}", e, art).into()
+    })
+}
+
+/// The hull painted from a white panel.
+pub fn image(input: &str) -> Result<Image> {
+    Ok(Image::from_bitmap(&hull(&run_robot(
+        &parse(input)?,
+        1,
+        |_, _, _| (),
+    ))))
+}
+
+/// Records the robot painting the hull from a white panel.
+pub fn replay(input: &str, recorder: &mut Recorder) -> Result<()> {
+    let mut steps = Vec::new();
+    let painted = run_robot(&parse(input)?, 1, |panel, color, robot| {
+        steps.push((panel, color, robot))
+    });
+
+    // the frames cover the whole painted area, with the same orientation
+    // as the hull
+    let bounds = get_bounds(&painted);
+    let to_pos =
+        |(x, y): (i32, i32)| grid::Pos::new((y - bounds.min.y) as i64, (x - bounds.min.x) as i64);
+    let color = |c| if c == 1 { Rgb::WHITE } else { Rgb::BLACK };
+    let mut hull = Image::new(
+        (bounds.max.y - bounds.min.y + 1) as usize,
+        (bounds.max.x - bounds.min.x + 1) as usize,
+        Rgb(40, 40, 40),
+    );
+    hull.set(to_pos((0, 0)), color(1));
+
+    for (idx, (panel, c, robot)) in steps.iter().enumerate() {
+        if recorder.is_full() {
+            break;
+        }
+        hull.set(to_pos(*panel), color(*c));
+        let draw = || {
+            let mut frame = hull.clone();
+            frame.set(to_pos(*robot), Rgb(255, 0, 0));
+            frame
+        };
+        if idx + 1 == steps.len() {
+            recorder.finish(|| hull.clone());
+        } else {
+            recorder.record(draw);
+        }
+    }
+    Ok(())
+}
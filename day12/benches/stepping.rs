@@ -0,0 +1,246 @@
+//! Compare the bodies stored axis by axis with the former planets, which
+//! held all their coordinates, and the stepping of the axes with its
+//! former version, a plain loop over the pairs of bodies. The loops are
+//! found with the same search on both sides.
+//!
+//! The axes are also stepped with many bodies, where they switch to a
+//! vectorized loop over all the bodies, and have their loops found with
+//! and without threads.
+//!
+//! Run with `cargo bench`.
+
+use aoc19_rust_day12::{parse, Axis, System};
+use math::cycle;
+use std::fs;
+use std::time::{Duration, Instant};
+
+/// Number of steps timed.
+const STEPS: u64 = 1_000_000;
+
+/// Number of bodies of the axis stepped with many bodies.
+const MANY_BODIES: i64 = 16;
+
+/// Best time out of `iterations` runs of `f`.
+fn measure<T, F: FnMut() -> T>(iterations: usize, mut f: F) -> (Duration, T) {
+    let mut best = None;
+    let mut result = None;
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+        result = Some(f());
+        let elapsed = start.elapsed();
+        best = Some(best.map_or(elapsed, |b: Duration| b.min(elapsed)));
+    }
+    (best.unwrap(), result.unwrap())
+}
+
+fn compare<T, F, G>(name: &str, iterations: usize, before: F, after: G)
+where
+    T: PartialEq + std::fmt::Debug,
+    F: FnMut() -> T,
+    G: FnMut() -> T,
+{
+    let (t1, r1) = measure(iterations, before);
+    let (t2, r2) = measure(iterations, after);
+    assert_eq!(r1, r2);
+
+    println!(
+        "{:<12} before {:>10.3?}  after {:>10.3?}  speedup {:.2}x",
+        name,
+        t1,
+        t2,
+        t1.as_secs_f64() / t2.as_secs_f64()
+    );
+}
+
+/// The planets as they were stepped before, all their coordinates
+/// together.
+#[derive(Clone)]
+struct Planet {
+    position: [i64; 3],
+    velocity: [i64; 3],
+}
+
+fn planets(system: &System) -> Vec<Planet> {
+    (0..system.bodies())
+        .map(|b| {
+            let p = system.position(b);
+            Planet {
+                position: [p[0], p[1], p[2]],
+                velocity: [0; 3],
+            }
+        })
+        .collect()
+}
+
+fn advance_time(planets: &mut [Planet]) {
+    for i in 0..planets.len() {
+        for j in (i + 1)..planets.len() {
+            for c in 0..3 {
+                let g = compute_gravity_on_axis(planets[i].position[c], planets[j].position[c]);
+                planets[i].velocity[c] += g;
+                planets[j].velocity[c] -= g;
+            }
+        }
+    }
+    for planet in planets.iter_mut() {
+        for c in 0..3 {
+            planet.position[c] += planet.velocity[c];
+        }
+    }
+}
+
+/// An axis stepped as before, with loops over any number of bodies.
+#[derive(Clone, PartialEq)]
+struct FormerAxis {
+    positions: Vec<i64>,
+    velocities: Vec<i64>,
+}
+
+impl FormerAxis {
+    fn step(&mut self) {
+        let n = self.positions.len();
+        for i in 0..n {
+            for j in (i + 1)..n {
+                let g = compute_gravity_on_axis(self.positions[i], self.positions[j]);
+                self.velocities[i] += g;
+                self.velocities[j] -= g;
+            }
+        }
+        for (position, velocity) in self.positions.iter_mut().zip(&self.velocities) {
+            *position += *velocity;
+        }
+    }
+}
+
+/// Planets compared on a single coordinate, to find its loop.
+#[derive(Clone)]
+struct Projection {
+    planets: Vec<Planet>,
+    c: usize,
+}
+
+impl PartialEq for Projection {
+    fn eq(&self, other: &Self) -> bool {
+        let c = self.c;
+        self.planets
+            .iter()
+            .zip(&other.planets)
+            .all(|(p, q)| p.position[c] == q.position[c] && p.velocity[c] == q.velocity[c])
+    }
+}
+
+fn compute_gravity_on_axis(a: i64, b: i64) -> i64 {
+    if a < b {
+        1
+    } else if a > b {
+        -1
+    } else {
+        0
+    }
+}
+
+fn main() {
+    let system = parse(&fs::read_to_string("input.txt").unwrap()).unwrap();
+
+    compare(
+        "steps",
+        5,
+        || {
+            let mut planets = planets(&system);
+            for _ in 0..STEPS {
+                advance_time(&mut planets);
+            }
+            planets
+                .iter()
+                .map(|p| p.position.to_vec())
+                .collect::<Vec<_>>()
+        },
+        || {
+            let mut system = system.clone();
+            system.run(STEPS);
+            (0..system.bodies())
+                .map(|b| system.position(b))
+                .collect::<Vec<_>>()
+        },
+    );
+
+    compare(
+        "cycles",
+        3,
+        || {
+            (0..3)
+                .map(|c| {
+                    let projection = Projection {
+                        planets: planets(&system),
+                        c,
+                    };
+                    cycle::brent(&projection, |p| advance_time(&mut p.planets)).len
+                })
+                .collect::<Vec<_>>()
+        },
+        || {
+            system
+                .axes()
+                .iter()
+                .map(|axis| cycle::brent(axis, Axis::step).len)
+                .collect()
+        },
+    );
+    compare(
+        "axis step",
+        3,
+        || {
+            system
+                .axes()
+                .iter()
+                .map(|axis| {
+                    let former = FormerAxis {
+                        positions: axis.positions().to_vec(),
+                        velocities: axis.velocities().to_vec(),
+                    };
+                    cycle::brent(&former, FormerAxis::step).len
+                })
+                .collect::<Vec<_>>()
+        },
+        || {
+            system
+                .axes()
+                .iter()
+                .map(|axis| cycle::brent(axis, Axis::step).len)
+                .collect()
+        },
+    );
+
+    // bodies spread around 0, at rest
+    let positions: Vec<i64> = (0..MANY_BODIES).map(|i| (i * 37) % 23 - 11).collect();
+    let axis = Axis::new(positions.clone());
+    compare(
+        "many bodies",
+        5,
+        || {
+            let mut former = FormerAxis {
+                velocities: vec![0; positions.len()],
+                positions: positions.clone(),
+            };
+            for _ in 0..STEPS / 10 {
+                former.step();
+            }
+            former.positions
+        },
+        || {
+            let mut axis = axis.clone();
+            for _ in 0..STEPS / 10 {
+                axis.step();
+            }
+            axis.positions().to_vec()
+        },
+    );
+
+    compare(
+        "threads",
+        10,
+        || system.axis_cycles(),
+        || system.par_axis_cycles(),
+    );
+}
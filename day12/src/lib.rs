@@ -0,0 +1,92 @@
+use export::replay::Recorder;
+use export::{Image, Rgb};
+use grid::Pos;
+
+pub mod series;
+pub mod system;
+
+pub use system::{Axis, Orbit, System};
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+/// Bodies at rest at the listed positions, which can have any number of
+/// coordinates as long as they all have the same.
+pub fn parse(input: &str) -> Result<System> {
+    Ok(System::new(&puzzle::vectors(input)?)?)
+}
+
+/// Number of steps simulated in the first part.
+const TLAST: u64 = 1000;
+
+pub fn day12a(system: &System) -> Result<String> {
+    let mut system = system.clone();
+    system.run(TLAST);
+    Ok(system.energy().to_string())
+}
+
+pub fn day12b(system: &System) -> Result<String> {
+    let cycle = system
+        .cycle()
+        .ok_or("cycle length does not fit in 64 bits")?;
+
+    // the system loops back to its initial state
+    Ok(cycle.first_repeat().to_string())
+}
+
+/// Records the bodies during the steps of the first part, projected on
+/// the first two axes.
+pub fn replay(input: &str, recorder: &mut Recorder) -> Result<()> {
+    const COLORS: [Rgb; 4] = [
+        Rgb(255, 80, 80),
+        Rgb(80, 255, 80),
+        Rgb(80, 160, 255),
+        Rgb(255, 220, 60),
+    ];
+
+    let mut system = parse(input)?;
+    let mut states = vec![system.clone()];
+    for _ in 0..TLAST {
+        system.step();
+        states.push(system.clone());
+    }
+
+    // positions along an axis, 0 when there are too few dimensions
+    let coords = |system: &System, axis: usize| -> Vec<i64> {
+        match system.axes().get(axis) {
+            Some(a) => a.positions().to_vec(),
+            None => vec![0; system.bodies()],
+        }
+    };
+
+    // the frames cover every position reached
+    let all = |axis| states.iter().flat_map(move |s| coords(s, axis));
+    let min_x = all(0).min().unwrap_or(0);
+    let max_x = all(0).max().unwrap_or(0);
+    let min_y = all(1).min().unwrap_or(0);
+    let max_y = all(1).max().unwrap_or(0);
+
+    let draw = |system: &System| {
+        let mut frame = Image::new(
+            (max_x - min_x + 1) as usize,
+            (max_y - min_y + 1) as usize,
+            Rgb::BLACK,
+        );
+        let xs = coords(system, 0);
+        let ys = coords(system, 1);
+        for ((x, y), color) in xs.iter().zip(&ys).zip(COLORS.iter().cycle()) {
+            frame.set(Pos::new(x - min_x, y - min_y), *color);
+        }
+        frame
+    };
+
+    let (last, states) = states.split_last().ok_or("no state to record")?;
+    for system in states {
+        if recorder.is_full() {
+            return Ok(());
+        }
+        recorder.record(|| draw(system));
+    }
+    recorder.finish(|| draw(last));
+    Ok(())
+}
@@ -0,0 +1,209 @@
+//! Time series of the energies and trajectories of the bodies, to plot
+//! how a system evolves.
+use crate::System;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// Which steps make the series.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// Number of steps simulated.
+    pub steps: u64,
+    /// Keeps one step out of `every`, the initial one included.
+    pub every: u64,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            steps: 1000,
+            every: 1,
+        }
+    }
+}
+
+/// A body at some step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Body {
+    pub position: Vec<i64>,
+    pub velocity: Vec<i64>,
+    pub potential: i64,
+    pub kinetic: i64,
+}
+
+impl Body {
+    pub fn energy(&self) -> i64 {
+        self.potential * self.kinetic
+    }
+}
+
+/// All the bodies at some step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sample {
+    pub step: u64,
+    pub bodies: Vec<Body>,
+}
+
+impl Sample {
+    fn new(step: u64, system: &System) -> Self {
+        let bodies = (0..system.bodies())
+            .map(|b| Body {
+                position: system.position(b),
+                velocity: system.velocity(b),
+                potential: system.potential_energy(b),
+                kinetic: system.kinetic_energy(b),
+            })
+            .collect();
+        Self { step, bodies }
+    }
+
+    /// Total energy of the bodies.
+    pub fn energy(&self) -> i64 {
+        self.bodies.iter().map(Body::energy).sum()
+    }
+}
+
+/// Samples of `system` from its initial step up to `options.steps`.
+pub fn samples(system: &System, options: Options) -> impl Iterator<Item = Sample> {
+    let mut system = system.clone();
+    let every = options.every.max(1);
+    let mut step = Some(0);
+    std::iter::from_fn(move || {
+        let current = step?;
+        let sample = Sample::new(current, &system);
+        // nothing is simulated past the last sample
+        step = current
+            .checked_add(every)
+            .filter(|next| *next <= options.steps);
+        if let Some(next) = step {
+            system.run(next - current);
+        }
+        Some(sample)
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Json,
+}
+
+impl Format {
+    /// The format matching the extension of `path`.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<Format> {
+        let ext = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "csv" => Some(Format::Csv),
+            "json" => Some(Format::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Name of an axis: x, y and z when there are at most three of them.
+fn axis_name(axis: usize, dimensions: usize) -> String {
+    if dimensions <= 3 {
+        ["x", "y", "z"][axis].to_string()
+    } else {
+        format!("x{}", axis)
+    }
+}
+
+/// Writes one line per body and step, after a header naming the columns.
+pub fn write_csv<I, W>(samples: I, dimensions: usize, mut out: W) -> io::Result<()>
+where
+    I: IntoIterator<Item = Sample>,
+    W: Write,
+{
+    let axes: Vec<_> = (0..dimensions).map(|a| axis_name(a, dimensions)).collect();
+    write!(out, "step,body")?;
+    for axis in &axes {
+        write!(out, ",{}", axis)?;
+    }
+    for axis in &axes {
+        write!(out, ",v{}", axis)?;
+    }
+    writeln!(out, ",potential,kinetic,energy")?;
+
+    for sample in samples {
+        for (i, body) in sample.bodies.iter().enumerate() {
+            write!(out, "{},{}", sample.step, i)?;
+            for c in body.position.iter().chain(&body.velocity) {
+                write!(out, ",{}", c)?;
+            }
+            writeln!(
+                out,
+                ",{},{},{}",
+                body.potential,
+                body.kinetic,
+                body.energy()
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes an array with one object per step, on its own line.
+pub fn write_json<I, W>(samples: I, mut out: W) -> io::Result<()>
+where
+    I: IntoIterator<Item = Sample>,
+    W: Write,
+{
+    let list = |v: &[i64]| {
+        let items: Vec<_> = v.iter().map(i64::to_string).collect();
+        format!("[{}]", items.join(","))
+    };
+
+    write!(out, "[")?;
+    for (n, sample) in samples.into_iter().enumerate() {
+        let bodies: Vec<_> = sample
+            .bodies
+            .iter()
+            .map(|b| {
+                format!(
+                    "{{\"position\":{},\"velocity\":{},\"potential\":{},\"kinetic\":{},\"energy\":{}}}",
+                    list(&b.position),
+                    list(&b.velocity),
+                    b.potential,
+                    b.kinetic,
+                    b.energy()
+                )
+            })
+            .collect();
+        write!(
+            out,
+            "{}\n{{\"step\":{},\"energy\":{},\"bodies\":[{}]}}",
+            if n == 0 { "" } else { "," },
+            sample.step,
+            sample.energy(),
+            bodies.join(",")
+        )?;
+    }
+    writeln!(out, "\n]")
+}
+
+/// Saves the series of `system` in the format picked from the extension
+/// of `path`, returning the number of samples.
+pub fn save<P: AsRef<Path>>(system: &System, options: Options, path: P) -> io::Result<usize> {
+    let path = path.as_ref();
+    let format = Format::from_path(path).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "unknown series format for {}, expected csv or json",
+                path.display()
+            ),
+        )
+    })?;
+
+    let mut count = 0;
+    let samples = samples(system, options).inspect(|_| count += 1);
+    let mut out = BufWriter::new(File::create(path)?);
+    match format {
+        Format::Csv => write_csv(samples, system.dimensions(), &mut out)?,
+        Format::Json => write_json(samples, &mut out)?,
+    }
+    out.flush()?;
+    Ok(count)
+}
@@ -0,0 +1,343 @@
+//! Simulation of bodies attracting each other, in any number of
+//! dimensions.
+//!
+//! Gravity only compares the coordinates of the bodies on a same axis, so
+//! every axis evolves independently of the others. The system is thus
+//! stored axis by axis, each of which can be stepped, and looped over, on
+//! its own.
+use math::cycle::{self, Cycle};
+use std::convert::TryFrom;
+use std::fmt;
+use std::thread;
+
+/// From this many bodies, an axis is stepped by summing the pulls of
+/// all the bodies on each of them, the null pull of a body on itself
+/// included: twice the comparisons of going over the pairs, but in
+/// branchless loops over slices, which get vectorized and pay off from
+/// about eight bodies.
+const SWEEP_BODIES: usize = 8;
+
+/// Positions and velocities of all the bodies along one axis.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Axis {
+    positions: Vec<i64>,
+    velocities: Vec<i64>,
+}
+
+impl Axis {
+    /// Axis with the bodies at `positions`, at rest.
+    pub fn new(positions: Vec<i64>) -> Self {
+        let velocities = vec![0; positions.len()];
+        Self {
+            positions,
+            velocities,
+        }
+    }
+
+    pub fn positions(&self) -> &[i64] {
+        &self.positions
+    }
+
+    pub fn velocities(&self) -> &[i64] {
+        &self.velocities
+    }
+
+    /// Applies gravity between every pair of bodies, then moves them.
+    pub fn step(&mut self) {
+        let positions = &mut self.positions[..];
+        let velocities = &mut self.velocities[..];
+        // with as few bodies as the moons of the puzzle, the loops over
+        // the pairs are unrolled, which more than halves the time of a
+        // step
+        match positions.len() {
+            2 => step_unrolled::<2>(positions, velocities),
+            3 => step_unrolled::<3>(positions, velocities),
+            4 => step_unrolled::<4>(positions, velocities),
+            n if n < SWEEP_BODIES => step_pairs(positions, velocities),
+            _ => step_sweep(positions, velocities),
+        }
+    }
+
+    /// The loop of the axis.
+    ///
+    /// A step is undone by stepping the mirrored state, where the bodies
+    /// are moved back by their velocities and the velocities negated. The
+    /// sequence is thus a pure loop, starting right away. An axis at rest
+    /// is its own mirror, which halves the search: when it is at rest
+    /// again after `k` steps, the next `k` steps mirror the previous ones
+    /// back to the initial state, and likewise in `2k - 1` steps when the
+    /// velocities after `k` steps are the opposite of those before.
+    pub fn cycle(&self) -> Cycle {
+        if self.velocities.iter().any(|v| *v != 0) {
+            return cycle::brent(self, Axis::step);
+        }
+
+        let mut axis = self.clone();
+        let mut previous = axis.velocities.clone();
+        let mut k = 0;
+        loop {
+            axis.step();
+            k += 1;
+            if axis
+                .velocities
+                .iter()
+                .zip(&previous)
+                .all(|(v, p)| *v == -*p)
+            {
+                return Cycle {
+                    start: 0,
+                    len: 2 * k - 1,
+                };
+            }
+            if axis.velocities.iter().all(|v| *v == 0) {
+                return Cycle {
+                    start: 0,
+                    len: 2 * k,
+                };
+            }
+            previous.copy_from_slice(&axis.velocities);
+        }
+    }
+}
+
+/// Steps exactly `N` bodies, see [`step_pairs`].
+fn step_unrolled<const N: usize>(positions: &mut [i64], velocities: &mut [i64]) {
+    let fixed = (
+        <&mut [i64; N]>::try_from(&mut *positions),
+        <&mut [i64; N]>::try_from(&mut *velocities),
+    );
+    match fixed {
+        (Ok(positions), Ok(velocities)) => step_pairs(positions, velocities),
+        _ => step_pairs(positions, velocities),
+    }
+}
+
+/// Applies gravity between every pair of bodies, then moves them.
+#[inline(always)]
+fn step_pairs(positions: &mut [i64], velocities: &mut [i64]) {
+    let n = positions.len();
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let g = gravity(positions[i], positions[j]);
+            velocities[i] += g;
+            velocities[j] -= g;
+        }
+    }
+    for (position, velocity) in positions.iter_mut().zip(velocities.iter()) {
+        *position += *velocity;
+    }
+}
+
+/// Sums the pulls of all the bodies on every body, then moves them,
+/// see [`SWEEP_BODIES`].
+fn step_sweep(positions: &mut [i64], velocities: &mut [i64]) {
+    {
+        let positions = &*positions;
+        let velocities = &mut velocities[..positions.len()];
+        for q in positions {
+            for (velocity, p) in velocities.iter_mut().zip(positions) {
+                *velocity += gravity(*p, *q);
+            }
+        }
+    }
+    for (position, velocity) in positions.iter_mut().zip(velocities.iter()) {
+        *position += *velocity;
+    }
+}
+
+/// Pull on a body at `a` from a body at `b`, as long as they are less
+/// than 2^63 apart.
+#[inline(always)]
+fn gravity(a: i64, b: i64) -> i64 {
+    // the sign bits of the difference and of its opposite, as 64-bit
+    // comparisons do not vectorize on every target while shifts do
+    let d = b.wrapping_sub(a);
+    ((d.wrapping_neg() as u64 >> 63) as i64) - ((d as u64 >> 63) as i64)
+}
+
+/// Bodies attracting each other, made of one [`Axis`] per dimension.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct System {
+    bodies: usize,
+    axes: Vec<Axis>,
+}
+
+impl System {
+    /// System with bodies at rest at `positions`, which must all have the
+    /// same number of coordinates.
+    pub fn new(positions: &[Vec<i64>]) -> Result<Self, String> {
+        let dimensions = positions.first().map_or(0, Vec::len);
+        if let Some(p) = positions.iter().find(|p| p.len() != dimensions) {
+            return Err(format!(
+                "expected {} coordinates, found {}",
+                dimensions,
+                p.len()
+            ));
+        }
+
+        let axes = (0..dimensions)
+            .map(|d| Axis::new(positions.iter().map(|p| p[d]).collect()))
+            .collect();
+        Ok(Self {
+            bodies: positions.len(),
+            axes,
+        })
+    }
+
+    /// Number of bodies.
+    pub fn bodies(&self) -> usize {
+        self.bodies
+    }
+
+    /// Number of dimensions, each of which is an axis.
+    pub fn dimensions(&self) -> usize {
+        self.axes.len()
+    }
+
+    pub fn axes(&self) -> &[Axis] {
+        &self.axes
+    }
+
+    pub fn step(&mut self) {
+        for axis in &mut self.axes {
+            axis.step();
+        }
+    }
+
+    pub fn run(&mut self, steps: u64) {
+        for axis in &mut self.axes {
+            for _ in 0..steps {
+                axis.step();
+            }
+        }
+    }
+
+    pub fn position(&self, body: usize) -> Vec<i64> {
+        self.axes.iter().map(|a| a.positions[body]).collect()
+    }
+
+    pub fn velocity(&self, body: usize) -> Vec<i64> {
+        self.axes.iter().map(|a| a.velocities[body]).collect()
+    }
+
+    /// Sum of the absolute values of the coordinates of a body.
+    pub fn potential_energy(&self, body: usize) -> i64 {
+        self.axes.iter().map(|a| a.positions[body].abs()).sum()
+    }
+
+    /// Sum of the absolute values of the velocity of a body.
+    pub fn kinetic_energy(&self, body: usize) -> i64 {
+        self.axes.iter().map(|a| a.velocities[body].abs()).sum()
+    }
+
+    /// Energy of a body, the product of its potential and kinetic
+    /// energies.
+    pub fn body_energy(&self, body: usize) -> i64 {
+        self.potential_energy(body) * self.kinetic_energy(body)
+    }
+
+    /// Total energy of the bodies.
+    pub fn energy(&self) -> i64 {
+        (0..self.bodies).map(|b| self.body_energy(b)).sum()
+    }
+
+    /// The loop of every axis.
+    pub fn axis_cycles(&self) -> Vec<Cycle> {
+        self.axes.iter().map(Axis::cycle).collect()
+    }
+
+    /// The loop of every axis, each found in its own thread.
+    pub fn par_axis_cycles(&self) -> Vec<Cycle> {
+        thread::scope(|scope| {
+            let handles: Vec<_> = self
+                .axes
+                .iter()
+                .map(|axis| scope.spawn(move || axis.cycle()))
+                .collect();
+            handles
+                .into_iter()
+                .map(|h| h.join().expect("axis thread panicked"))
+                .collect()
+        })
+    }
+
+    /// The loop of the whole system, which repeats once all its axes do
+    /// at the same step. None if its length does not fit in 64 bits.
+    pub fn cycle(&self) -> Option<Cycle> {
+        cycle::product(self.axis_cycles())
+    }
+
+    /// Same as [`cycle`](Self::cycle), the axes being looped over in
+    /// parallel.
+    pub fn par_cycle(&self) -> Option<Cycle> {
+        cycle::product(self.par_axis_cycles())
+    }
+
+    /// The loops of the system, to jump to any of its future steps.
+    pub fn orbit(&self) -> Orbit {
+        Orbit {
+            initial: self.clone(),
+            cycles: self.axis_cycles(),
+        }
+    }
+}
+
+/// A system along with the loop of each of its axes.
+#[derive(Debug, Clone)]
+pub struct Orbit {
+    initial: System,
+    cycles: Vec<Cycle>,
+}
+
+impl Orbit {
+    pub fn axis_cycles(&self) -> &[Cycle] {
+        &self.cycles
+    }
+
+    /// The loop of the whole system, none if its length does not fit in
+    /// 64 bits.
+    pub fn cycle(&self) -> Option<Cycle> {
+        cycle::product(self.cycles.iter().copied())
+    }
+
+    /// The system after `n` steps, every axis being stepped at most the
+    /// length of its loop.
+    pub fn state_at(&self, n: u64) -> System {
+        let axes = self
+            .initial
+            .axes
+            .iter()
+            .zip(&self.cycles)
+            .map(|(axis, cycle)| cycle.state_at(axis, Axis::step, n))
+            .collect();
+        System {
+            bodies: self.initial.bodies,
+            axes,
+        }
+    }
+
+    /// Total energy of the system after `n` steps.
+    pub fn energy_at(&self, n: u64) -> i64 {
+        self.state_at(n).energy()
+    }
+}
+
+impl fmt::Display for System {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |v: Vec<i64>| {
+            v.iter()
+                .map(|c| format!("{:3}", c))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        for body in 0..self.bodies {
+            writeln!(
+                f,
+                "pos=<{}>, vel=<{}>",
+                join(self.position(body)),
+                join(self.velocity(body))
+            )?;
+        }
+        Ok(())
+    }
+}
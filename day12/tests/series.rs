@@ -0,0 +1,80 @@
+use aoc19_rust_day12::series::{self, Options};
+use aoc19_rust_day12::System;
+
+fn example() -> System {
+    System::new(&[
+        vec![-1, 0, 2],
+        vec![2, -10, -7],
+        vec![4, -8, 8],
+        vec![3, 5, -1],
+    ])
+    .unwrap()
+}
+
+#[test]
+fn sampling() {
+    let options = Options {
+        steps: 10,
+        every: 3,
+    };
+    let samples: Vec<_> = series::samples(&example(), options).collect();
+    let steps: Vec<_> = samples.iter().map(|s| s.step).collect();
+    assert_eq!(steps, vec![0, 3, 6, 9]);
+
+    // as many steps as wanted, without overflowing
+    let options = Options {
+        steps: u64::MAX,
+        every: 1,
+    };
+    assert_eq!(
+        series::samples(&example(), options)
+            .nth(10)
+            .unwrap()
+            .energy(),
+        179
+    );
+
+    let last = series::samples(&example(), Options::default())
+        .nth(10)
+        .unwrap();
+    assert_eq!(last.step, 10);
+    assert_eq!(last.energy(), 179);
+    let body = &last.bodies[0];
+    assert_eq!(body.position, vec![2, 1, -3]);
+    assert_eq!(body.velocity, vec![-3, -2, 1]);
+    assert_eq!((body.potential, body.kinetic, body.energy()), (6, 6, 36));
+}
+
+#[test]
+fn csv_and_json() {
+    let options = Options { steps: 1, every: 1 };
+    let system = System::new(&[vec![0, 1], vec![3, 1]]).unwrap();
+
+    let mut csv = Vec::new();
+    series::write_csv(series::samples(&system, options), 2, &mut csv).unwrap();
+    assert_eq!(
+        String::from_utf8(csv).unwrap(),
+        "step,body,x,y,vx,vy,potential,kinetic,energy
+0,0,0,1,0,0,1,0,0
+0,1,3,1,0,0,4,0,0
+1,0,1,1,1,0,2,1,2
+1,1,2,1,-1,0,3,1,3
+"
+    );
+
+    let mut json = Vec::new();
+    series::write_json(series::samples(&system, options), &mut json).unwrap();
+    let json = String::from_utf8(json).unwrap();
+    let lines: Vec<_> = json.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert_eq!(
+        lines[2],
+        "{\"step\":1,\"energy\":5,\"bodies\":[\
+         {\"position\":[1,1],\"velocity\":[1,0],\"potential\":2,\"kinetic\":1,\"energy\":2},\
+         {\"position\":[2,1],\"velocity\":[-1,0],\"potential\":3,\"kinetic\":1,\"energy\":3}]}"
+    );
+
+    let mut empty = Vec::new();
+    series::write_json(None, &mut empty).unwrap();
+    assert_eq!(empty, b"[\n]\n");
+}
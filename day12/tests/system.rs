@@ -0,0 +1,116 @@
+use aoc19_rust_day12::{parse, System};
+use math::cycle::{self, Cycle};
+
+const EXAMPLE1: &str = "<x=-1, y=0, z=2>
+<x=2, y=-10, z=-7>
+<x=4, y=-8, z=8>
+<x=3, y=5, z=-1>
+";
+
+const EXAMPLE2: &str = "<x=-8, y=-10, z=0>
+<x=5, y=5, z=10>
+<x=2, y=-7, z=3>
+<x=9, y=-8, z=-3>
+";
+
+#[test]
+fn examples() {
+    let mut system = parse(EXAMPLE1).unwrap();
+    system.run(10);
+    assert_eq!(system.position(0), vec![2, 1, -3]);
+    assert_eq!(system.velocity(0), vec![-3, -2, 1]);
+    assert_eq!(system.energy(), 179);
+    assert_eq!(
+        parse(EXAMPLE1).unwrap().cycle(),
+        Some(Cycle {
+            start: 0,
+            len: 2772
+        })
+    );
+
+    let mut system = parse(EXAMPLE2).unwrap();
+    system.run(100);
+    assert_eq!(system.energy(), 1940);
+    assert_eq!(
+        parse(EXAMPLE2).unwrap().cycle().map(|c| c.first_repeat()),
+        Some(4_686_774_924)
+    );
+}
+
+#[test]
+fn any_dimension() {
+    // two bodies on a line oscillate around each other
+    let mut system = System::new(&[vec![0], vec![3]]).unwrap();
+    assert_eq!(system.dimensions(), 1);
+    system.step();
+    assert_eq!(system.position(0), vec![1]);
+    assert_eq!(system.position(1), vec![2]);
+    assert_eq!(system.cycle().map(|c| c.len), Some(8));
+
+    let system = System::new(&[vec![1, 2, 3, 4], vec![0, 0, 0, 0], vec![-1, 5, 2, 7]]).unwrap();
+    assert_eq!(system.dimensions(), 4);
+    assert_eq!(system.bodies(), 3);
+    assert_eq!(system.energy(), 0);
+
+    assert!(System::new(&[vec![1, 2], vec![3]]).is_err());
+}
+
+#[test]
+fn many_bodies() {
+    // whatever the loop used to step them, the bodies follow the pulls
+    // of every pair
+    for n in 1..12 {
+        let mut positions: Vec<i64> = (0..n).map(|i| (i * 37) % 11 - 5).collect();
+        let mut velocities = vec![0; n as usize];
+        let mut system =
+            System::new(&positions.iter().map(|p| vec![*p]).collect::<Vec<_>>()).unwrap();
+
+        for _ in 0..20 {
+            for (v, p) in velocities.iter_mut().zip(&positions) {
+                *v += positions.iter().map(|q| (q - p).signum()).sum::<i64>();
+            }
+            for (p, v) in positions.iter_mut().zip(&velocities) {
+                *p += v;
+            }
+            system.step();
+            assert_eq!(system.axes()[0].positions(), &positions[..]);
+            assert_eq!(system.axes()[0].velocities(), &velocities[..]);
+        }
+    }
+}
+
+#[test]
+fn reversibility() {
+    // the halved search agrees with a plain one, from rest or not, and
+    // with loops of odd lengths
+    for example in &[EXAMPLE1, EXAMPLE2] {
+        let mut system = parse(example).unwrap();
+        for _ in 0..3 {
+            for axis in system.axes() {
+                assert_eq!(axis.cycle(), cycle::brent(axis, |a| a.step()));
+            }
+            system.step();
+        }
+    }
+    let system = parse(EXAMPLE2).unwrap();
+    assert_eq!(system.par_axis_cycles(), system.axis_cycles());
+    assert_eq!(system.par_cycle(), system.cycle());
+
+    let system = System::new(&[vec![0], vec![1], vec![5], vec![7]]).unwrap();
+    let axis = &system.axes()[0];
+    assert_eq!(axis.cycle(), cycle::brent(axis, |a| a.step()));
+    assert_eq!(System::new(&[vec![4]]).unwrap().cycle().unwrap().len, 1);
+}
+
+#[test]
+fn future_energy() {
+    let system = parse(EXAMPLE1).unwrap();
+    let orbit = system.orbit();
+    let mut stepped = system.clone();
+    for n in 0..200 {
+        assert_eq!(orbit.energy_at(n), stepped.energy(), "step {}", n);
+        stepped.step();
+    }
+    assert_eq!(orbit.state_at(2772), system);
+    assert_eq!(orbit.energy_at(10 + 2772 * 1_000_000), 179);
+}
@@ -0,0 +1,168 @@
+use export::replay::Recorder;
+use export::{Image, Rgb};
+use grid::{GrowingGrid, Pos};
+use intcode::{Intcode, Patch};
+use std::fmt;
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+pub fn parse(input: &str) -> Result<Vec<i64>> {
+    Ok(puzzle::comma_separated(input)?)
+}
+
+struct Screen {
+    tiles: GrowingGrid<TileType>,
+    score: i64,
+    ball_pos_x: i64,
+    paddle_pos_x: i64,
+}
+
+impl Screen {
+    fn new(memory: &[i64]) -> Self {
+        let mut s = Self {
+            tiles: GrowingGrid::new(TileType::Empty),
+            score: -1,
+            ball_pos_x: 0,
+            paddle_pos_x: 0,
+        };
+        s.fill(&Intcode::new(memory).run(&[]));
+        s
+    }
+
+    fn fill(&mut self, output: &[i64]) {
+        for tile in output.chunks_exact(3) {
+            let (x, y, t) = (tile[0], tile[1], tile[2]);
+
+            if x == -1 && y == 0 {
+                self.score = t;
+                continue;
+            }
+
+            let typ = match t {
+                0 => TileType::Empty,
+                1 => TileType::Wall,
+                2 => TileType::Block,
+                3 => {
+                    self.paddle_pos_x = x;
+                    TileType::Paddle
+                }
+                4 => {
+                    self.ball_pos_x = x;
+                    TileType::Ball
+                }
+                _ => panic!("invalid tile type {}", t),
+            };
+
+            self.tiles.set(Pos::new(x, y), typ);
+        }
+    }
+}
+
+impl fmt::Display for Screen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.tiles)
+    }
+}
+
+#[derive(Clone)]
+enum TileType {
+    Empty,
+    Wall,
+    Block,
+    Paddle,
+    Ball,
+}
+
+impl fmt::Display for TileType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", match self {
+            Self::Empty => ' ',
+            Self::Wall  => 'W',
+            Self::Block => 'O',
+            Self::Paddle => '_',
+            Self::Ball  => 'X',
+        })
+    }
+}
+
+pub fn day13a(memory: &[i64]) -> Result<String> {
+    let screen = Screen::new(memory);
+    let nb_blocks = screen
+        .tiles
+        .iter()
+        .filter(|(_, t)| matches!(t, TileType::Block))
+        .count();
+    Ok(nb_blocks.to_string())
+}
+
+/// Plays the game until it ends or `on_frame` returns false, calling it
+/// after every move of the joystick with whether the game is over.
+/// Returns the last score.
+fn play<F>(memory: &[i64], mut on_frame: F) -> Result<i64>
+where
+    F: FnMut(&Screen, bool) -> bool,
+{
+    let mut screen = Screen::new(memory);
+    let mut program = Intcode::new(memory);
+    let mut free_play = Patch::new("free-play");
+    free_play.poke_expecting(0, 1, 2);
+    free_play.apply(&mut program)?;
+
+    let mut input = 1;
+    loop {
+        let output = program.run(&[input]);
+        screen.fill(&output);
+
+        if !on_frame(&screen, program.is_done()) || program.is_done() {
+            break;
+        }
+
+        input = if screen.paddle_pos_x < screen.ball_pos_x {
+            1
+        } else if screen.paddle_pos_x > screen.ball_pos_x {
+            -1
+        } else {
+            0
+        };
+    }
+
+    Ok(screen.score)
+}
+
+pub fn day13b(memory: &[i64]) -> Result<String> {
+    let score = play(memory, |_, _| true)?;
+    Ok(score.to_string())
+}
+
+fn draw(screen: &Screen) -> Image {
+    Image::from_growing_grid(&screen.tiles, |tile| match tile {
+        TileType::Empty => Rgb::BLACK,
+        TileType::Wall => Rgb(128, 128, 128),
+        TileType::Block => Rgb(200, 120, 40),
+        TileType::Paddle => Rgb::WHITE,
+        TileType::Ball => Rgb(255, 0, 0),
+    })
+}
+
+/// The screen when the game starts.
+pub fn image(input: &str) -> Result<Image> {
+    Ok(draw(&Screen::new(&parse(input)?)))
+}
+
+/// Records the screen after every move of the joystick, until the game
+/// is won.
+pub fn replay(input: &str, recorder: &mut Recorder) -> Result<()> {
+    let memory = parse(input)?;
+    recorder.record(|| draw(&Screen::new(&memory)));
+
+    play(&memory, |screen, over| {
+        if over {
+            recorder.finish(|| draw(screen));
+        } else {
+            recorder.record(|| draw(screen));
+        }
+        !recorder.is_full()
+    })?;
+    Ok(())
+}
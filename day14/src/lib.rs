@@ -1,51 +1,38 @@
-use lazy_static::lazy_static;
-use regex::Regex;
+use puzzle::{Quantity, Reaction};
 use std::collections::HashMap;
-use std::io;
-use std::io::Read;
-use std::str::FromStr;
 
 type Error = Box<dyn std::error::Error>;
 type Result<T> = std::result::Result<T, Error>;
 
-fn main() -> Result<()> {
-    let mut input = String::new();
-    io::stdin().read_to_string(&mut input)?;
-
-    let recipes: Vec<_> = input
-        .lines()
-        .map(|line| line.parse::<Recipe>().unwrap())
-        .collect();
+pub fn parse(input: &str) -> Result<Vec<Reaction<'_>>> {
+    Ok(puzzle::reactions(input)?)
+}
 
-    let recipes_map: HashMap<_, _> = recipes
+fn get_recipes_map<'a>(recipes: &'a [Reaction<'a>]) -> HashMap<String, ValCompound<'a>> {
+    recipes
         .iter()
         .map(|r| {
             (
-                r.product.element.to_owned(),
+                r.output.chemical.to_owned(),
                 ValCompound {
-                    ingredients: &r.ingredients,
-                    product_count: r.product.count,
+                    ingredients: &r.inputs,
+                    product_count: r.output.count,
                 },
             )
         })
-        .collect();
-
-    day14a(&recipes_map);
-    day14b(&recipes_map);
-    Ok(())
+        .collect()
 }
 
-fn day14a(recipes_map: &HashMap<String, ValCompound>) {
+pub fn day14a(recipes: &[Reaction]) -> Result<String> {
+    let recipes_map = &get_recipes_map(recipes);
     let mut rest: HashMap<&str, u64> = HashMap::new();
 
     produce_fuel(recipes_map, 1, false, &mut rest);
-    println!(
-        "day14a number of ore required: {}",
-        rest.get("ORE").unwrap()
-    );
+    Ok(rest.get("ORE").ok_or("no ORE required")?.to_string())
 }
 
-fn day14b(recipes_map: &HashMap<String, ValCompound>) {
+pub fn day14b(recipes: &[Reaction]) -> Result<String> {
+    let recipes_map = &get_recipes_map(recipes);
     let mut min = 1;
     let mut max = 2;
 
@@ -68,7 +55,7 @@ fn day14b(recipes_map: &HashMap<String, ValCompound>) {
             max = m;
         }
     }
-    println!("day14b number of fuel produced: {}", min);
+    Ok(min.to_string())
 }
 
 fn can_produce_fuel(recipes_map: &HashMap<String, ValCompound>, fuel_qty: u64) -> bool {
@@ -101,9 +88,9 @@ fn produce_fuel<'r, 'm: 'r>(
                     }
                 }
                 if qty != 0 {
-                    let factor = (qty + vc.product_count - 1) / vc.product_count;
+                    let factor = qty.div_ceil(vc.product_count);
                     for c in vc.ingredients {
-                        want.push((&c.element, c.count * factor));
+                        want.push((c.chemical, c.count * factor));
                     }
                     let r = vc.product_count * factor - qty;
                     if r != 0 {
@@ -129,39 +116,6 @@ fn produce_fuel<'r, 'm: 'r>(
 }
 
 struct ValCompound<'a> {
-    ingredients: &'a Vec<Compound>,
+    ingredients: &'a [Quantity<'a>],
     product_count: u64,
 }
-
-struct Compound {
-    element: String,
-    count: u64,
-}
-
-struct Recipe {
-    ingredients: Vec<Compound>,
-    product: Compound,
-}
-
-impl FromStr for Recipe {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self> {
-        lazy_static! {
-            static ref RE: Regex = Regex::new(r"(\d+) (\w+)").unwrap();
-        }
-        let mut compounds: Vec<_> = RE
-            .captures_iter(s)
-            .map(|cap| Compound {
-                element: cap[2].to_owned(),
-                count: cap[1].parse::<u64>().unwrap(),
-            })
-            .collect();
-        let product = compounds.pop().unwrap();
-
-        Ok(Recipe {
-            ingredients: compounds,
-            product,
-        })
-    }
-}
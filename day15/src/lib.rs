@@ -0,0 +1,185 @@
+use export::replay::Recorder;
+use export::{Image, Rgb};
+use grid::{GrowingGrid, Pos};
+use intcode::Intcode;
+use std::collections::HashMap;
+use std::fmt;
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+/// Maps the area with the repair droid program of the input.
+pub fn parse(input: &str) -> Result<Grid> {
+    explore(&puzzle::comma_separated(input)?)
+}
+
+const DROID: Pos = Pos::new(0, 0);
+
+/// Movement commands of the droid, and the corresponding offsets.
+const MOVES: [(i64, Pos); 4] = [
+    (1, Pos::UP),
+    (2, Pos::DOWN),
+    (3, Pos::LEFT),
+    (4, Pos::RIGHT),
+];
+
+pub fn day15a(grid: &Grid) -> Result<String> {
+    let search = search::bfs(Some(DROID), grid.passable_neighbors());
+    let distance = search
+        .distance(&grid.oxygen()?)
+        .ok_or("oxygen system not reachable")?;
+    Ok(distance.to_string())
+}
+
+/// Maps the whole area, by searching it with a droid for every reached
+/// position.
+fn explore(state: &[i64]) -> Result<Grid> {
+    let mut grid = Grid::new();
+    let mut droids = HashMap::new();
+    let mut error = None;
+
+    grid.map.set(DROID, TileType::Empty);
+    droids.insert(DROID, Intcode::new(state));
+
+    search::bfs(Some(DROID), |pos: &Pos| {
+        let mut next = Vec::new();
+        let droid = match droids.remove(pos) {
+            Some(droid) => droid,
+            None => return next,
+        };
+
+        for (input, offset) in MOVES.iter() {
+            let npos = *pos + *offset;
+            if let TileType::Unknown = grid.map.get(npos) {
+                let mut droid = droid.clone();
+                match move_droid(&mut droid, *input) {
+                    Ok(tile) => {
+                        grid.discovered.push(npos);
+                        grid.map.set(npos, tile.clone());
+                        match tile {
+                            TileType::Wall => continue,
+                            TileType::OxygenSystem => grid.oxygen = Some(npos),
+                            _ => (),
+                        }
+                        droids.insert(npos, droid);
+                    }
+                    Err(e) => {
+                        error.get_or_insert(e);
+                        continue;
+                    }
+                }
+            }
+            if !matches!(grid.map.get(npos), TileType::Wall) {
+                next.push(npos);
+            }
+        }
+        next
+    });
+
+    match error {
+        Some(e) => Err(e),
+        None => Ok(grid),
+    }
+}
+
+fn move_droid(droid: &mut Intcode, input: i64) -> Result<TileType> {
+    let output = droid.try_run(&[input])?;
+    match output.first().ok_or("droid did not report a status")? {
+        0 => Ok(TileType::Wall),
+        1 => Ok(TileType::Empty),
+        2 => Ok(TileType::OxygenSystem),
+        status => Err(format!("unknown output {}", status).into()),
+    }
+}
+
+pub fn day15b(grid: &Grid) -> Result<String> {
+    let search = search::bfs(Some(grid.oxygen()?), grid.passable_neighbors());
+    let (_, minutes) = search.furthest().ok_or("oxygen system not reachable")?;
+    Ok(minutes.to_string())
+}
+
+fn color(tile: &TileType) -> Rgb {
+    match tile {
+        TileType::Unknown => Rgb::BLACK,
+        TileType::Empty => Rgb::WHITE,
+        TileType::Wall => Rgb(128, 128, 128),
+        TileType::OxygenSystem => Rgb(0, 120, 255),
+    }
+}
+
+/// The explored area.
+pub fn image(input: &str) -> Result<Image> {
+    let grid = parse(input)?;
+    Ok(Image::from_growing_grid(&grid.map, color))
+}
+
+/// Records the exploration of the area, one tile at a time.
+pub fn replay(input: &str, recorder: &mut Recorder) -> Result<()> {
+    let grid = parse(input)?;
+
+    // the frames cover the whole area
+    let (lo, _) = grid.map.bounds().ok_or("nothing explored")?;
+    let mut frame = Image::from_growing_grid(&grid.map, |_| color(&TileType::Unknown));
+    frame.set(DROID - lo, color(&TileType::Empty));
+
+    let (last, tiles) = grid.discovered.split_last().ok_or("nothing explored")?;
+    for pos in tiles {
+        if recorder.is_full() {
+            return Ok(());
+        }
+        frame.set(*pos - lo, color(grid.map.get(*pos)));
+        recorder.record(|| frame.clone());
+    }
+    frame.set(*last - lo, color(grid.map.get(*last)));
+    recorder.finish(|| frame);
+    Ok(())
+}
+
+#[derive(Clone)]
+enum TileType {
+    Unknown,
+    Empty,
+    Wall,
+    OxygenSystem,
+}
+
+/// The explored area.
+pub struct Grid {
+    map: GrowingGrid<TileType>,
+    oxygen: Option<Pos>,
+    /// Tiles in the order the droid discovered them.
+    discovered: Vec<Pos>,
+}
+
+impl Grid {
+    fn new() -> Self {
+        Grid {
+            map: GrowingGrid::new(TileType::Unknown),
+            oxygen: None,
+            discovered: Vec::new(),
+        }
+    }
+
+    fn oxygen(&self) -> Result<Pos> {
+        self.oxygen.ok_or_else(|| "no oxygen system found".into())
+    }
+
+    fn passable_neighbors(&self) -> impl FnMut(&Pos) -> Vec<Pos> + '_ {
+        search::growing_grid_neighbors(&self.map, |tile| match tile {
+            TileType::Empty | TileType::OxygenSystem => true,
+            TileType::Unknown | TileType::Wall => false,
+        })
+    }
+}
+
+impl fmt::Display for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let render = self.map.render(|tile| match tile {
+            TileType::Unknown => ' ',
+            TileType::Empty => '.',
+            TileType::Wall => '#',
+            TileType::OxygenSystem => 'O',
+        });
+        write!(f, "{}", render)
+    }
+}
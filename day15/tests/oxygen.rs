@@ -0,0 +1,19 @@
+use aoc19_rust_day15::{day15a, day15b, parse};
+
+// a droid surrounded by walls: reads a move, reports a wall, forever
+const WALLED: &str = "3,7,104,0,1105,1,0,0";
+
+#[test]
+fn no_oxygen_system() {
+    let state = parse(WALLED).unwrap();
+    for part in &[day15a, day15b] {
+        let error = part(&state).unwrap_err();
+        assert_eq!(error.to_string(), "no oxygen system found");
+    }
+}
+
+#[test]
+fn crashing_droid() {
+    // reads a move, then hits an invalid opcode
+    assert!(parse("3,7,42").is_err());
+}
@@ -1,36 +1,33 @@
-use std::io;
-use std::io::Read;
-
 type Error = Box<dyn std::error::Error>;
 type Result<T> = std::result::Result<T, Error>;
 
-fn main() -> Result<()> {
-    let mut input = String::new();
-    io::stdin().read_to_string(&mut input)?;
-
-    let input: Vec<u32> = input
-        .trim()
-        .chars()
-        .map(|c| c.to_digit(10).unwrap())
-        .collect();
-
-    day16a(input.clone());
-    day16b(&input);
-    Ok(())
+pub fn parse(input: &str) -> Result<Vec<u32>> {
+    Ok(puzzle::digits(input)?)
 }
 
-fn day16a(mut input: Vec<u32>) {
+pub fn day16a(signal: &[u32]) -> Result<String> {
+    let mut input = signal.to_vec();
+    if input.len() < 8 {
+        return Err("signal is shorter than 8 digits".into());
+    }
+
     for _ in 0..100 {
         let mut new_input = (0..input.len()).map(|idx| apply_fft(&input, idx)).collect();
         std::mem::swap(&mut input, &mut new_input);
     }
-    println!("day16a: {}", vec_to_u32(&input[0..8]));
+    Ok(vec_to_u32(&input[0..8]).to_string())
 }
 
-fn day16b(orig: &Vec<u32>) {
+pub fn day16b(orig: &[u32]) -> Result<String> {
     let full_len = orig.len() * 10000;
+    if orig.len() < 7 {
+        return Err("signal is shorter than 7 digits".into());
+    }
 
     let offset = vec_to_u32(&orig[0..7]);
+    if offset + 8 > full_len {
+        return Err(format!("message offset {} is out of the signal", offset).into());
+    }
     let rest_len = full_len - offset;
     let mut input = Vec::new();
     let mut i = 1;
@@ -46,10 +43,10 @@ fn day16b(orig: &Vec<u32>) {
             *v = acc % 10;
         }
     }
-    println!("day16b: {:?}", vec_to_u32(input.iter().rev().take(8)));
+    Ok(vec_to_u32(input.iter().rev().take(8)).to_string())
 }
 
-fn apply_fft(input: &Vec<u32>, lvl: usize) -> u32 {
+fn apply_fft(input: &[u32], lvl: usize) -> u32 {
     const FILTER: [i32; 4] = [0, 1, 0, -1];
 
     input
@@ -58,7 +55,7 @@ fn apply_fft(input: &Vec<u32>, lvl: usize) -> u32 {
         .fold(0, |acc: i32, (idx, v)| {
             acc + (*v as i32) * FILTER[((idx + 1) / (lvl + 1)) % 4]
         })
-        .abs() as u32
+        .unsigned_abs()
         % 10
 }
 
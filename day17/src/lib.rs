@@ -0,0 +1,77 @@
+use export::{Image, Rgb};
+use grid::Grid;
+use intcode::{Intcode, Patch};
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+pub fn parse(input: &str) -> Result<Vec<i64>> {
+    Ok(puzzle::comma_separated(input)?)
+}
+
+pub fn day17a(memory: &[i64]) -> Result<String> {
+    let mut pgm = Intcode::new(memory);
+    let output: String = pgm.run(&[]).iter().map(|i| *i as u8 as char).collect();
+
+    // fill a grid with positions of the scaffold, the robot being on one
+    // of them unless it fell off
+    let grid = Grid::parse(&output, |c| match c {
+        '#' => Some(true),
+        '.' | '^' | 'v' | '<' | '>' | 'X' => Some(false),
+        _ => None,
+    })?;
+
+    // find intersections, where a scaffold has at least 3 neighbors
+    let acc: i64 = grid
+        .iter()
+        .filter(|(pos, is_scaffold)| {
+            **is_scaffold && grid.neighbors4(*pos).filter(|n| grid[*n]).count() >= 3
+        })
+        .map(|(pos, _)| pos.x * pos.y)
+        .sum();
+
+    Ok(acc.to_string())
+}
+
+/// The view of the cameras: scaffolds, open space and the robot.
+pub fn image(input: &str) -> Result<Image> {
+    let mut pgm = Intcode::new(&parse(input)?);
+    let output: String = pgm.run(&[]).iter().map(|i| *i as u8 as char).collect();
+    let view = Grid::parse(&output, |c| match c {
+        '#' | '.' | '^' | 'v' | '<' | '>' | 'X' => Some(c),
+        _ => None,
+    })?;
+
+    Ok(Image::from_grid(&view, |c| match c {
+        '#' => Rgb::WHITE,
+        '.' => Rgb::BLACK,
+        _ => Rgb(255, 0, 0),
+    }))
+}
+
+pub fn day17b(memory: &[i64]) -> Result<String> {
+    let input: Vec<i64> = [
+        "A,B,A,C,B,C,B,C,A,C",
+        "R,12,L,10,R,12",
+        "L,8,R,10,R,6",
+        "R,12,L,10,R,10,L,8",
+        "n",
+        "",
+    ]
+    .join("\n")
+    .chars()
+    .map(|v| v as i64)
+    .collect();
+
+    // wake up the robot
+    let mut pgm = Intcode::new(memory);
+    let mut wake_up = Patch::new("wake-up");
+    wake_up.poke_expecting(0, 1, 2);
+    wake_up.apply(&mut pgm)?;
+
+    let out = pgm.run(&input);
+    Ok(out
+        .last()
+        .ok_or("robot did not report any dust")?
+        .to_string())
+}
@@ -0,0 +1,43 @@
+use intcode::{Intcode, Patch};
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+pub fn parse(input: &str) -> Result<Vec<i64>> {
+    Ok(puzzle::comma_separated(input)?)
+}
+
+/// Sets the noun and verb, the operands of the first instruction, which
+/// are 0 in the original program.
+fn noun_verb(noun: i64, verb: i64) -> Patch {
+    let mut patch = Patch::new("noun-verb");
+    patch.poke_expecting(1, 0, noun);
+    patch.poke_expecting(2, 0, verb);
+    patch
+}
+
+pub fn day2a(state: &[i64]) -> Result<String> {
+    let mut intcode = Intcode::new(state);
+    noun_verb(12, 2).apply(&mut intcode)?;
+    intcode.run(&[]);
+
+    Ok(intcode.memory[0].to_string())
+}
+
+pub fn day2b(state: &[i64]) -> Result<String> {
+    let mut patch = noun_verb(0, 0);
+
+    for noun in 0..100 {
+        for verb in 0..100 {
+            patch.pokes[0].value = noun;
+            patch.pokes[1].value = verb;
+            let mut intcode = Intcode::new(state);
+            patch.apply(&mut intcode)?;
+            intcode.run(&[]);
+            if intcode.memory[0] == 19690720 {
+                return Ok((100 * noun + verb).to_string());
+            }
+        }
+    }
+
+    Err("no noun,verb pair found".into())
+}
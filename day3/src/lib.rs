@@ -0,0 +1,160 @@
+use grid::{Grid, Pos};
+use puzzle::{Direction, Segment};
+use std::cmp::{max, min};
+
+type Error = Box<dyn std::error::Error>;
+type Result<T> = std::result::Result<T, Error>;
+
+/// Map marked with the first circuit, and the second circuit.
+pub fn parse(input: &str) -> Result<(Map, Vec<Segment>)> {
+    let mut lines = puzzle::wires(input)?;
+    if lines.len() != 2 {
+        return Err("expected two circuits".into());
+    }
+
+    // compute the bounds of the grid, positions of the second circuit
+    // outside of it cannot be intersections
+    let mut bounds = Bounds::new();
+    bounds.add_circuit(&lines[0]);
+
+    let mut map = bounds.to_map();
+    map.mark_circuit(&lines[0]);
+
+    Ok((map, lines.pop().unwrap()))
+}
+
+// {{{ Bounds
+
+struct Bounds {
+    min: Pos,
+    max: Pos,
+}
+
+impl Bounds {
+    pub fn new() -> Self {
+        Self {
+            min: Pos::new(0, 0),
+            max: Pos::new(0, 0),
+        }
+    }
+
+    pub fn add_circuit(&mut self, circuit: &[Segment]) {
+        let mut pos = Pos::new(0, 0);
+
+        for mov in circuit {
+            pos += direction_offset(mov.direction) * i64::from(mov.length);
+            self.min = Pos::new(min(self.min.x, pos.x), min(self.min.y, pos.y));
+            self.max = Pos::new(max(self.max.x, pos.x), max(self.max.y, pos.y));
+        }
+    }
+
+    pub fn to_map(&self) -> Map {
+        let size = self.max - self.min;
+
+        Map {
+            grid: Grid::new(size.x as usize + 1, size.y as usize + 1, 0),
+            cp: Pos::new(0, 0) - self.min,
+        }
+    }
+}
+
+// }}}
+// {{{ Map
+
+fn direction_offset(direction: Direction) -> Pos {
+    match direction {
+        Direction::Up => Pos::UP,
+        Direction::Down => Pos::DOWN,
+        Direction::Left => Pos::LEFT,
+        Direction::Right => Pos::RIGHT,
+    }
+}
+
+fn apply_movement<F>(pos: &mut Pos, mov: &Segment, mut cb: F)
+where
+    F: FnMut(Pos),
+{
+    let offset = direction_offset(mov.direction);
+
+    for _ in 0..mov.length {
+        *pos += offset;
+        cb(*pos)
+    }
+}
+
+/// Number of steps of the first circuit to reach every position.
+pub struct Map {
+    grid: Grid<u32>,
+    /// Position of the central port.
+    cp: Pos,
+}
+
+impl Map {
+    fn steps(&self, pos: Pos) -> u32 {
+        self.grid.get(pos).copied().unwrap_or(0)
+    }
+
+    pub fn mark_circuit(&mut self, circuit: &[Segment]) {
+        let mut pos = self.cp;
+        let mut steps = 0;
+
+        for mov in circuit {
+            apply_movement(&mut pos, mov, |pos| {
+                steps += 1;
+                self.grid[pos] = steps;
+            });
+        }
+    }
+
+    fn find_min_distance(&self, circuit: &[Segment]) -> Option<i64> {
+        let mut pos = self.cp;
+        let mut min_distance = None;
+
+        for mov in circuit {
+            apply_movement(&mut pos, mov, |pos| {
+                if self.steps(pos) > 0 {
+                    let distance = pos.manhattan(self.cp);
+                    min_distance.replace(match min_distance {
+                        None => distance,
+                        Some(d) => min(d, distance),
+                    });
+                }
+            });
+        }
+        min_distance
+    }
+
+    fn find_min_steps(&self, circuit: &[Segment]) -> Option<u32> {
+        let mut pos = self.cp;
+        let mut steps = 0;
+        let mut min_steps = None;
+
+        for mov in circuit {
+            apply_movement(&mut pos, mov, |pos| {
+                steps += 1;
+                if self.steps(pos) > 0 {
+                    let total_steps = steps + self.steps(pos);
+                    min_steps.replace(match min_steps {
+                        None => total_steps,
+                        Some(d) => min(d, total_steps),
+                    });
+                }
+            });
+        }
+        min_steps
+    }
+}
+
+// }}}
+
+pub fn day3a((map, circuit2): &(Map, Vec<Segment>)) -> Result<String> {
+    let min_distance = map.find_min_distance(circuit2);
+
+    Ok(min_distance.ok_or("no intersection")?.to_string())
+}
+
+pub fn day3b((map, circuit2): &(Map, Vec<Segment>)) -> Result<String> {
+    let min_steps = map.find_min_steps(circuit2);
+
+    Ok(min_steps.ok_or("no intersection")?.to_string())
+}
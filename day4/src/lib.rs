@@ -1,22 +1,15 @@
-use std::io;
-use std::io::Read;
-
 type Error = Box<dyn std::error::Error>;
 type Result<T> = std::result::Result<T, Error>;
 
-fn main() -> Result<()> {
-    let mut input = String::new();
-    io::stdin().read_to_string(&mut input)?;
-
-    let bounds: Vec<u32> = input
+pub fn parse(input: &str) -> Result<(u32, u32)> {
+    let bounds = input
         .split('-')
-        .map(|v| v.trim().parse::<u32>().unwrap())
-        .collect();
-    assert_eq!(bounds.len(), 2);
-
-    day4a(bounds[0], bounds[1]);
-    day4b(bounds[0], bounds[1]);
-    Ok(())
+        .map(|v| v.trim().parse::<u32>())
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    match bounds[..] {
+        [min, max] => Ok((min, max)),
+        _ => Err("expected a range".into()),
+    }
 }
 
 fn has_same_adjacent_digits(mut val: u32, larger_group_allowed: bool) -> bool {
@@ -42,7 +35,7 @@ fn has_same_adjacent_digits(mut val: u32, larger_group_allowed: bool) -> bool {
         last_digit.replace(digit);
         val /= 10;
     }
-    return group_len == 2;
+    group_len == 2
 }
 
 fn has_increasing_digits(mut val: u32) -> bool {
@@ -62,26 +55,26 @@ fn has_increasing_digits(mut val: u32) -> bool {
     true
 }
 
-fn day4a(minval: u32, maxval: u32) {
+pub fn day4a(&(minval, maxval): &(u32, u32)) -> Result<String> {
     let mut nb_possible_passwords = 0;
 
-    for val in minval..(maxval + 1) {
+    for val in minval..=maxval {
         if has_same_adjacent_digits(val, true) && has_increasing_digits(val) {
             nb_possible_passwords += 1;
         }
     }
 
-    println!("day4a: nb possible passwords: {}", nb_possible_passwords);
+    Ok(nb_possible_passwords.to_string())
 }
 
-fn day4b(minval: u32, maxval: u32) {
+pub fn day4b(&(minval, maxval): &(u32, u32)) -> Result<String> {
     let mut nb_possible_passwords = 0;
 
-    for val in minval..(maxval + 1) {
+    for val in minval..=maxval {
         if has_same_adjacent_digits(val, false) && has_increasing_digits(val) {
             nb_possible_passwords += 1;
         }
     }
 
-    println!("day4b: nb possible passwords: {}", nb_possible_passwords);
+    Ok(nb_possible_passwords.to_string())
 }
\ No newline at end of file
@@ -0,0 +1,26 @@
+use intcode::Intcode;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+pub fn parse(input: &str) -> Result<Vec<i64>> {
+    Ok(puzzle::comma_separated(input)?)
+}
+
+pub fn day5a(state: &[i64]) -> Result<String> {
+    let mut intcode = Intcode::new(state);
+    let output = intcode.try_run(&[1])?;
+    let (code, tests) = output.split_last().ok_or("no output")?;
+    if tests.iter().any(|v| *v != 0) {
+        return Err(format!("failed tests: {:?}", tests).into());
+    }
+    Ok(code.to_string())
+}
+
+pub fn day5b(state: &[i64]) -> Result<String> {
+    let mut intcode = Intcode::new(state);
+    let output = intcode.try_run(&[5])?;
+    match output.as_slice() {
+        [code] => Ok(code.to_string()),
+        _ => Err(format!("expected a single diagnostic code, got {:?}", output).into()),
+    }
+}
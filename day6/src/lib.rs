@@ -0,0 +1,31 @@
+use search::Graph;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+/// Orbits, as pairs of a star and one of its satellites.
+pub fn parse(input: &str) -> Result<Vec<(&str, &str)>> {
+    Ok(puzzle::pairs(input, ")")?)
+}
+
+pub fn day6a(orbits: &[(&str, &str)]) -> Result<String> {
+    // from star to its satellites
+    let graph = Graph::directed(orbits.iter().cloned());
+
+    // the distance of each object to the center of mass is its number of
+    // direct and indirect orbits
+    let search = search::bfs(graph.roots(), |obj| graph.neighbors(obj));
+    let total: u64 = search.distances().values().sum();
+
+    Ok(total.to_string())
+}
+
+pub fn day6b(orbits: &[(&str, &str)]) -> Result<String> {
+    let graph = Graph::undirected(orbits.iter().cloned());
+
+    let search = search::bfs(Some("YOU"), |obj| graph.neighbors(obj));
+    // transfers are between the objects orbited by YOU and SAN
+    match search.distance(&"SAN") {
+        Some(d) if d >= 2 => Ok((d - 2).to_string()),
+        _ => Err("no path from YOU to SAN".into()),
+    }
+}
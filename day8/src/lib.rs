@@ -0,0 +1,81 @@
+use export::Image;
+use grid::{Grid, Pos};
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+const WIDTH: usize = 25;
+const HEIGHT: usize = 6;
+
+// split input into layers
+pub fn parse(input: &str) -> Result<Vec<Vec<u32>>> {
+    let input = puzzle::digits(input)?;
+    if input.len() % (WIDTH * HEIGHT) != 0 {
+        return Err(format!("image size is not a multiple of {}", WIDTH * HEIGHT).into());
+    }
+    Ok(input
+        .chunks_exact(WIDTH * HEIGHT)
+        .map(|layer| layer.to_vec())
+        .collect())
+}
+
+fn count_digits(layer: &[u32], digit: u32) -> usize {
+    layer
+        .iter()
+        .fold(0, |acc, c| if *c == digit { acc + 1 } else { acc })
+}
+
+fn find_layer_fewest_zeroes(layers: &[Vec<u32>]) -> &[u32] {
+    let mut min_idx = 0;
+    let mut min = WIDTH * HEIGHT;
+
+    for (idx, layer) in layers.iter().enumerate() {
+        let nb_zeroes = count_digits(layer, 0);
+        if nb_zeroes < min {
+            min = nb_zeroes;
+            min_idx = idx;
+        }
+    }
+
+    &layers[min_idx]
+}
+
+pub fn day8a(layers: &[Vec<u32>]) -> Result<String> {
+    let layer = find_layer_fewest_zeroes(layers);
+
+    let nb_ones = count_digits(layer, 1);
+    let nb_twos = count_digits(layer, 2);
+
+    Ok((nb_ones * nb_twos).to_string())
+}
+
+/// Stacks the layers, the first non transparent pixel being visible.
+fn decode(layers: &[Vec<u32>]) -> Grid<bool> {
+    let mut image = vec![2; WIDTH * HEIGHT];
+
+    for layer in layers {
+        for (idx, c) in layer.iter().enumerate() {
+            if image[idx] == 2 {
+                image[idx] = *c;
+            }
+        }
+    }
+
+    let mut message = Grid::new(WIDTH, HEIGHT, false);
+    for (idx, c) in image.iter().enumerate() {
+        message[Pos::new((idx % WIDTH) as i64, (idx / WIDTH) as i64)] = *c == 1;
+    }
+    message
+}
+
+pub fn day8b(layers: &[Vec<u32>]) -> Result<String> {
+    let message = decode(layers);
+    ocr::recognize(&message).map_err(|e| {
+        let art = message.render(|lit| if *lit { 'X' } else { ' ' });
+        format!("{}\n{}", e, art).into()
+    })
+}
+
+/// The decoded image.
+pub fn image(input: &str) -> Result<Image> {
+    Ok(Image::from_bitmap(&decode(&parse(input)?)))
+}
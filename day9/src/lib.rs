@@ -0,0 +1,25 @@
+use intcode::Intcode;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+pub fn parse(input: &str) -> Result<Vec<i64>> {
+    Ok(puzzle::comma_separated(input)?)
+}
+
+pub fn day9a(state: &[i64]) -> Result<String> {
+    let mut intcode = Intcode::new(state);
+    let output = intcode.try_run(&[1])?;
+
+    // any output before the keycode is a malfunctioning opcode
+    match output.as_slice() {
+        [keycode] => Ok(keycode.to_string()),
+        _ => Err(format!("malfunctioning opcodes: {:?}", output).into()),
+    }
+}
+
+pub fn day9b(state: &[i64]) -> Result<String> {
+    let mut intcode = Intcode::new(state);
+    let output = intcode.try_run(&[2])?;
+
+    Ok(output.last().ok_or("no coordinates output")?.to_string())
+}
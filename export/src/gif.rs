@@ -0,0 +1,167 @@
+//! An encoder for animated GIF files, see
+//! <https://www.w3.org/Graphics/GIF/spec-gif89a.txt>.
+use crate::{Image, Rgb};
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+/// Largest code of the LZW compression.
+const MAX_CODE: u16 = 4095;
+
+/// Writes the frames as a looping animation, showing each frame for
+/// `delay` hundredths of a second.
+pub fn write<W: Write>(frames: &[Image], scale: usize, delay: u16, mut out: W) -> io::Result<()> {
+    let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidInput, msg.to_owned());
+
+    if frames.is_empty() {
+        return Err(invalid("no frames to write"));
+    }
+    let (mut width, mut height) = (0, 0);
+    for frame in frames {
+        let scaled = (
+            frame.width.checked_mul(scale),
+            frame.height.checked_mul(scale),
+        );
+        match scaled {
+            (Some(w), Some(h)) if w <= u16::MAX as usize && h <= u16::MAX as usize => {
+                width = width.max(w);
+                height = height.max(h);
+            }
+            _ => return Err(invalid("image too large for gif")),
+        }
+    }
+
+    let mut palette = Vec::new();
+    let mut indices = HashMap::new();
+    for c in frames.iter().flat_map(|f| f.pixels.iter()) {
+        indices.entry(*c).or_insert_with(|| {
+            palette.push(*c);
+            palette.len() - 1
+        });
+    }
+    if palette.len() > 256 {
+        return Err(invalid("more than 256 colors in the frames"));
+    }
+    // the color table holds a power of two colors, at least 2
+    let bits = (usize::BITS - (palette.len().max(2) - 1).leading_zeros()) as u8;
+    palette.resize(1 << bits, Rgb::BLACK);
+
+    out.write_all(b"GIF89a")?;
+    out.write_all(&(width as u16).to_le_bytes())?;
+    out.write_all(&(height as u16).to_le_bytes())?;
+    // global color table, of 2^bits colors
+    out.write_all(&[0x80 | ((bits - 1) << 4) | (bits - 1), 0, 0])?;
+    for c in &palette {
+        out.write_all(&[c.0, c.1, c.2])?;
+    }
+    // loop forever
+    out.write_all(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00")?;
+
+    let min_code_size = bits.max(2);
+    for frame in frames {
+        // graphic control: restore the background after the frame
+        out.write_all(&[0x21, 0xf9, 0x04, 0x08])?;
+        out.write_all(&delay.to_le_bytes())?;
+        out.write_all(&[0, 0])?;
+
+        let (w, h) = (frame.width * scale, frame.height * scale);
+        out.write_all(&[0x2c, 0, 0, 0, 0])?;
+        out.write_all(&(w as u16).to_le_bytes())?;
+        out.write_all(&(h as u16).to_le_bytes())?;
+        out.write_all(&[0])?;
+
+        let pixels: Vec<u8> = frame
+            .scaled_rows(scale)
+            .flatten()
+            .map(|c| indices[&c] as u8)
+            .collect();
+        out.write_all(&[min_code_size])?;
+        for block in lzw(&pixels, min_code_size).chunks(255) {
+            out.write_all(&[block.len() as u8])?;
+            out.write_all(block)?;
+        }
+        out.write_all(&[0])?;
+    }
+
+    out.write_all(&[0x3b])
+}
+
+/// Compresses color indices with the variable length LZW of GIF.
+fn lzw(indices: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+
+    let mut out = BitWriter::default();
+    let mut codes: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next = end + 1;
+    let mut size = min_code_size + 1;
+    out.write(clear, size);
+
+    let mut prefix = None;
+    for &index in indices {
+        let p = match prefix {
+            None => {
+                prefix = Some(index as u16);
+                continue;
+            }
+            Some(p) => p,
+        };
+        if let Some(code) = codes.get(&(p, index)) {
+            prefix = Some(*code);
+            continue;
+        }
+
+        out.write(p, size);
+        codes.insert((p, index), next);
+        next += 1;
+        // the decoder adds its codes one step later, hence the strict
+        // comparison
+        if next > 1 << size && size < 12 {
+            size += 1;
+        }
+        if next > MAX_CODE {
+            out.write(clear, size);
+            codes.clear();
+            next = end + 1;
+            size = min_code_size + 1;
+        }
+        prefix = Some(index as u16);
+    }
+
+    if let Some(p) = prefix {
+        out.write(p, size);
+        // the decoder adds a code when reading this one, which may widen
+        // the end code
+        if next == 1 << size && size < 12 {
+            size += 1;
+        }
+    }
+    out.write(end, size);
+    out.finish()
+}
+
+/// Packs codes, least significant bits first.
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    acc: u32,
+    nb_bits: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, size: u8) {
+        self.acc |= (code as u32) << self.nb_bits;
+        self.nb_bits += size;
+        while self.nb_bits >= 8 {
+            self.bytes.push(self.acc as u8);
+            self.acc >>= 8;
+            self.nb_bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.nb_bits > 0 {
+            self.bytes.push(self.acc as u8);
+        }
+        self.bytes
+    }
+}
@@ -0,0 +1,190 @@
+//! Export of grids as image files.
+//!
+//! A grid is first turned into an [`Image`] by giving a color to each of
+//! its tiles, which can then be written as PBM, PPM, PNG or SVG, every
+//! tile being drawn as a square of `scale` pixels. The [`replay`] module
+//! records successive images of a simulation as an animation.
+use grid::{Grid, GrowingGrid, Pos};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+mod gif;
+mod netpbm;
+mod png;
+pub mod replay;
+mod svg;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+
+    fn is_dark(self) -> bool {
+        // perceived luminance, from ITU-R BT.601
+        let luma = 299 * self.0 as u32 + 587 * self.1 as u32 + 114 * self.2 as u32;
+        luma < 128 * 1000
+    }
+}
+
+/// Format of an image file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Black and white netpbm bitmap, dark colors being black.
+    Pbm,
+    /// Color netpbm pixmap.
+    Ppm,
+    Png,
+    Svg,
+}
+
+impl Format {
+    /// The format matching the extension of `path`.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<Format> {
+        let ext = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "pbm" => Some(Format::Pbm),
+            "ppm" => Some(Format::Ppm),
+            "png" => Some(Format::Png),
+            "svg" => Some(Format::Svg),
+            _ => None,
+        }
+    }
+}
+
+/// A picture, one color per tile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, color: Rgb) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![color; width * height],
+        }
+    }
+
+    /// Colors every tile of the grid with `palette`.
+    pub fn from_grid<T, F>(grid: &Grid<T>, palette: F) -> Self
+    where
+        F: Fn(&T) -> Rgb,
+    {
+        Self {
+            width: grid.width(),
+            height: grid.height(),
+            pixels: grid.iter().map(|(_, t)| palette(t)).collect(),
+        }
+    }
+
+    /// Lit pixels are white, the others black.
+    pub fn from_bitmap(grid: &Grid<bool>) -> Self {
+        Self::from_grid(grid, |lit| if *lit { Rgb::WHITE } else { Rgb::BLACK })
+    }
+
+    /// Colors every tile inside the bounds of the grid with `palette`.
+    pub fn from_growing_grid<T, F>(grid: &GrowingGrid<T>, palette: F) -> Self
+    where
+        F: Fn(&T) -> Rgb,
+    {
+        let (width, height) = match grid.bounds() {
+            Some((lo, hi)) => ((hi.x - lo.x + 1) as usize, (hi.y - lo.y + 1) as usize),
+            None => (0, 0),
+        };
+        Self {
+            width,
+            height,
+            pixels: grid.iter().map(|(_, t)| palette(t)).collect(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Color of the pixel at `pos`, none if outside of the image.
+    pub fn get(&self, pos: Pos) -> Option<Rgb> {
+        if pos.x >= 0 && pos.y >= 0 && (pos.x as usize) < self.width {
+            self.pixels
+                .get(pos.y as usize * self.width + pos.x as usize)
+                .copied()
+        } else {
+            None
+        }
+    }
+
+    /// Sets the color of the pixel at `pos`, returning false if outside
+    /// of the image.
+    pub fn set(&mut self, pos: Pos, color: Rgb) -> bool {
+        if pos.x >= 0 && pos.y >= 0 && (pos.x as usize) < self.width {
+            if let Some(pixel) = self
+                .pixels
+                .get_mut(pos.y as usize * self.width + pos.x as usize)
+            {
+                *pixel = color;
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Rows of pixels, from the top.
+    fn rows(&self) -> impl Iterator<Item = &[Rgb]> {
+        self.pixels.chunks(self.width.max(1)).take(self.height)
+    }
+
+    /// Rows of the image scaled up by `scale`.
+    fn scaled_rows(&self, scale: usize) -> impl Iterator<Item = Vec<Rgb>> + '_ {
+        self.rows().flat_map(move |row| {
+            let row: Vec<_> = row
+                .iter()
+                .flat_map(|c| std::iter::repeat_n(*c, scale))
+                .collect();
+            std::iter::repeat_n(row, scale)
+        })
+    }
+
+    /// Writes the image in `format`, every tile being `scale` pixels wide.
+    pub fn write<W: Write>(&self, format: Format, scale: usize, out: W) -> io::Result<()> {
+        if scale == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "scale must be at least 1",
+            ));
+        }
+        match format {
+            Format::Pbm => netpbm::write_pbm(self, scale, out),
+            Format::Ppm => netpbm::write_ppm(self, scale, out),
+            Format::Png => png::write(self, scale, out),
+            Format::Svg => svg::write(self, scale, out),
+        }
+    }
+
+    /// Saves the image to `path`, in the format of its extension.
+    pub fn save<P: AsRef<Path>>(&self, path: P, scale: usize) -> io::Result<()> {
+        let path = path.as_ref();
+        let format = Format::from_path(path).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "unknown image format for {}, expected pbm, ppm, png or svg",
+                    path.display()
+                ),
+            )
+        })?;
+
+        let mut out = BufWriter::new(File::create(path)?);
+        self.write(format, scale, &mut out)?;
+        out.flush()
+    }
+}
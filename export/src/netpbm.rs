@@ -0,0 +1,33 @@
+//! Binary netpbm formats, see <http://netpbm.sourceforge.net/doc/>.
+use crate::Image;
+use std::io::{self, Write};
+
+pub fn write_pbm<W: Write>(image: &Image, scale: usize, mut out: W) -> io::Result<()> {
+    writeln!(out, "P4\n{} {}", image.width * scale, image.height * scale)?;
+    for row in image.scaled_rows(scale) {
+        // 8 pixels per byte, the last one padded, 1 being black
+        for pixels in row.chunks(8) {
+            let byte = pixels
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| c.is_dark())
+                .fold(0u8, |acc, (i, _)| acc | (0x80 >> i));
+            out.write_all(&[byte])?;
+        }
+    }
+    Ok(())
+}
+
+pub fn write_ppm<W: Write>(image: &Image, scale: usize, mut out: W) -> io::Result<()> {
+    writeln!(
+        out,
+        "P6\n{} {}\n255",
+        image.width * scale,
+        image.height * scale
+    )?;
+    for row in image.scaled_rows(scale) {
+        let bytes: Vec<u8> = row.iter().flat_map(|c| vec![c.0, c.1, c.2]).collect();
+        out.write_all(&bytes)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,143 @@
+//! Recording of simulations, frame by frame.
+//!
+//! A simulation offers a frame to the [`Recorder`] at every step, which
+//! keeps some of them depending on its [`Options`]. The frames are then
+//! written as an animated GIF or as numbered image files.
+use crate::{gif, Format, Image};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// Keeps one frame out of `every`, starting with the first.
+    pub every: usize,
+    /// Stops recording after this many frames.
+    pub max_frames: Option<usize>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            every: 1,
+            max_frames: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Recorder {
+    options: Options,
+    frames: Vec<Image>,
+    /// Number of frames offered so far.
+    offered: usize,
+}
+
+impl Recorder {
+    pub fn new(options: Options) -> Self {
+        Self {
+            options,
+            frames: Vec::new(),
+            offered: 0,
+        }
+    }
+
+    /// Whether no more frames will be kept, so that the simulation can
+    /// stop.
+    pub fn is_full(&self) -> bool {
+        match self.options.max_frames {
+            Some(max) => self.frames.len() >= max,
+            None => false,
+        }
+    }
+
+    /// Offers the next frame. `draw` is only called if the frame is kept.
+    pub fn record<F>(&mut self, draw: F)
+    where
+        F: FnOnce() -> Image,
+    {
+        let keep = self.offered.is_multiple_of(self.options.every.max(1));
+        self.offered += 1;
+        if keep && !self.is_full() {
+            self.frames.push(draw());
+        }
+    }
+
+    /// Offers the final frame, which is kept even if it would have been
+    /// skipped, so that the animation ends on the final state.
+    pub fn finish<F>(&mut self, draw: F)
+    where
+        F: FnOnce() -> Image,
+    {
+        self.offered += 1;
+        if !self.is_full() {
+            self.frames.push(draw());
+        }
+    }
+
+    pub fn frames(&self) -> &[Image] {
+        &self.frames
+    }
+
+    /// Writes the frames as a looping GIF animation, each frame being
+    /// shown for `delay`.
+    pub fn write_gif<W: Write>(&self, scale: usize, delay: Duration, out: W) -> io::Result<()> {
+        if scale == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "scale must be at least 1",
+            ));
+        }
+        let centis = (delay.as_millis() / 10).min(u16::MAX as u128) as u16;
+        gif::write(&self.frames, scale, centis, out)
+    }
+
+    /// Saves the frames to `path`: a GIF animation if it ends with
+    /// `.gif`, otherwise one file per frame, `{}` in the path being
+    /// replaced by the number of the frame.
+    pub fn save(&self, path: &str, scale: usize, delay: Duration) -> io::Result<Vec<PathBuf>> {
+        if path.to_ascii_lowercase().ends_with(".gif") {
+            if let Some(dir) = Path::new(path).parent() {
+                fs::create_dir_all(dir)?;
+            }
+            let mut out = BufWriter::new(File::create(path)?);
+            self.write_gif(scale, delay, &mut out)?;
+            out.flush()?;
+            Ok(vec![PathBuf::from(path)])
+        } else {
+            self.save_frames(path, scale)
+        }
+    }
+
+    /// Saves every frame to its own file, in the format given by the
+    /// extension of `pattern`. `{}` in the pattern is replaced by the
+    /// number of the frame, padded with zeroes so that files sort in
+    /// order.
+    pub fn save_frames(&self, pattern: &str, scale: usize) -> io::Result<Vec<PathBuf>> {
+        let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidInput, msg);
+        if !pattern.contains("{}") {
+            return Err(invalid(format!(
+                "{} must contain {{}} to number the frames",
+                pattern
+            )));
+        }
+        let format = Format::from_path(pattern)
+            .ok_or_else(|| invalid(format!("unknown image format for {}", pattern)))?;
+
+        let digits = self.frames.len().saturating_sub(1).to_string().len();
+        let mut paths = Vec::with_capacity(self.frames.len());
+        for (idx, frame) in self.frames.iter().enumerate() {
+            let path = PathBuf::from(pattern.replace("{}", &format!("{:0w$}", idx, w = digits)));
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+
+            let mut out = BufWriter::new(File::create(&path)?);
+            frame.write(format, scale, &mut out)?;
+            out.flush()?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+}
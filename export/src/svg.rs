@@ -0,0 +1,60 @@
+use crate::{Image, Rgb};
+use std::io::{self, Write};
+
+/// Writes one rectangle per run of pixels of the same color in a row, on
+/// a background of the most common color.
+pub fn write<W: Write>(image: &Image, scale: usize, mut out: W) -> io::Result<()> {
+    let (width, height) = (image.width, image.height);
+    writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}" shape-rendering="crispEdges">"#,
+        width * scale,
+        height * scale,
+        width,
+        height
+    )?;
+
+    if let Some(background) = most_common(&image.pixels) {
+        writeln!(
+            out,
+            r#"<rect width="{}" height="{}" fill="{}"/>"#,
+            width,
+            height,
+            hex(background)
+        )?;
+
+        for (y, row) in image.rows().enumerate() {
+            let mut x = 0;
+            for run in row.chunk_by(|a, b| a == b) {
+                if run[0] != background {
+                    writeln!(
+                        out,
+                        r#"<rect x="{}" y="{}" width="{}" height="1" fill="{}"/>"#,
+                        x,
+                        y,
+                        run.len(),
+                        hex(run[0])
+                    )?;
+                }
+                x += run.len();
+            }
+        }
+    }
+    writeln!(out, "</svg>")
+}
+
+/// The most common color, the first one seen on ties.
+fn most_common(pixels: &[Rgb]) -> Option<Rgb> {
+    let mut counts: Vec<(Rgb, usize)> = Vec::new();
+    for c in pixels {
+        match counts.iter_mut().find(|(color, _)| color == c) {
+            Some((_, n)) => *n += 1,
+            None => counts.push((*c, 1)),
+        }
+    }
+    counts.iter().rev().max_by_key(|(_, n)| *n).map(|(c, _)| *c)
+}
+
+fn hex(c: Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", c.0, c.1, c.2)
+}
@@ -0,0 +1,155 @@
+use export::{Format, Image, Rgb};
+use grid::{Grid, GrowingGrid, Pos};
+
+const RED: Rgb = Rgb(255, 0, 0);
+
+/// A 3x2 image: a black, a white and a red pixel on each row.
+fn image() -> Image {
+    let grid = Grid::parse("bwr\nbwr\n", |c| match c {
+        'b' => Some(Rgb::BLACK),
+        'w' => Some(Rgb::WHITE),
+        'r' => Some(RED),
+        _ => None,
+    })
+    .unwrap();
+    Image::from_grid(&grid, |c| *c)
+}
+
+fn write(image: &Image, format: Format, scale: usize) -> Vec<u8> {
+    let mut out = Vec::new();
+    image.write(format, scale, &mut out).unwrap();
+    out
+}
+
+#[test]
+fn from_grids() {
+    let grid = Grid::parse("#.\n.#\n", |c| Some(c == '#')).unwrap();
+    let image = Image::from_bitmap(&grid);
+    assert_eq!((image.width(), image.height()), (2, 2));
+    assert_eq!(image.get(Pos::new(1, 1)), Some(Rgb::WHITE));
+    assert_eq!(image.get(Pos::new(1, 0)), Some(Rgb::BLACK));
+    assert_eq!(image.get(Pos::new(2, 0)), None);
+
+    let mut grid = GrowingGrid::new(false);
+    grid.set(Pos::new(-2, 3), true);
+    grid.set(Pos::new(1, 4), true);
+    let image = Image::from_growing_grid(&grid, |t| if *t { RED } else { Rgb::BLACK });
+    assert_eq!((image.width(), image.height()), (4, 2));
+    assert_eq!(image.get(Pos::new(0, 0)), Some(RED));
+    assert_eq!(image.get(Pos::new(3, 1)), Some(RED));
+    assert_eq!(image.get(Pos::new(1, 1)), Some(Rgb::BLACK));
+}
+
+#[test]
+fn netpbm() {
+    let image = image();
+
+    // red is dark enough to be black
+    let pbm = write(&image, Format::Pbm, 1);
+    assert_eq!(pbm, b"P4\n3 2\n\xa0\xa0");
+
+    let pbm = write(&image, Format::Pbm, 3);
+    let (header, bits) = pbm.split_at(7);
+    assert_eq!(header, b"P4\n9 6\n");
+    assert_eq!(bits, [0xe3, 0x80].repeat(6).as_slice());
+
+    let ppm = write(&image, Format::Ppm, 1);
+    let mut expected = b"P6\n3 2\n255\n".to_vec();
+    for _ in 0..2 {
+        expected.extend_from_slice(&[0, 0, 0, 255, 255, 255, 255, 0, 0]);
+    }
+    assert_eq!(ppm, expected);
+}
+
+/// Splits a png file into its chunks.
+fn split_chunks(mut png: &[u8]) -> Vec<(String, Vec<u8>)> {
+    assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+    png = &png[8..];
+
+    let mut chunks = Vec::new();
+    while !png.is_empty() {
+        let len = u32::from_be_bytes([png[0], png[1], png[2], png[3]]) as usize;
+        let kind = String::from_utf8(png[4..8].to_vec()).unwrap();
+        chunks.push((kind, png[8..8 + len].to_vec()));
+        png = &png[12 + len..];
+    }
+    chunks
+}
+
+/// Inflates a zlib stream made of stored blocks.
+fn inflate_stored(zlib: &[u8]) -> Vec<u8> {
+    assert_eq!(&zlib[..2], [0x78, 0x01]);
+    let mut data = Vec::new();
+    let mut pos = 2;
+    loop {
+        let last = zlib[pos] & 1 == 1;
+        assert_eq!(zlib[pos] >> 1, 0, "not a stored block");
+        let len = u16::from_le_bytes([zlib[pos + 1], zlib[pos + 2]]) as usize;
+        let nlen = u16::from_le_bytes([zlib[pos + 3], zlib[pos + 4]]) as usize;
+        assert_eq!(len ^ 0xffff, nlen);
+        data.extend_from_slice(&zlib[pos + 5..pos + 5 + len]);
+        pos += 5 + len;
+        if last {
+            break;
+        }
+    }
+    assert_eq!(pos + 4, zlib.len());
+    data
+}
+
+#[test]
+fn png() {
+    let png = write(&image(), Format::Png, 2);
+
+    // the crc of the empty IEND chunk is a well known constant
+    assert!(png.ends_with(b"\0\0\0\0IEND\xae\x42\x60\x82"));
+
+    let chunks = split_chunks(&png);
+    let kinds: Vec<_> = chunks.iter().map(|(k, _)| k.as_str()).collect();
+    assert_eq!(kinds, ["IHDR", "IDAT", "IEND"]);
+    assert_eq!(chunks[0].1, [0, 0, 0, 6, 0, 0, 0, 4, 8, 2, 0, 0, 0]);
+
+    let mut row = vec![0];
+    for c in &[[0, 0, 0], [255, 255, 255], [255, 0, 0]] {
+        row.extend_from_slice(c);
+        row.extend_from_slice(c);
+    }
+    assert_eq!(inflate_stored(&chunks[1].1), row.repeat(4));
+
+    // large images are split in several blocks
+    let image = Image::new(200, 200, RED);
+    let png = write(&image, Format::Png, 1);
+    let data = inflate_stored(&split_chunks(&png)[1].1);
+    assert_eq!(data.len(), 200 * (1 + 3 * 200));
+}
+
+#[test]
+fn svg() {
+    let svg = String::from_utf8(write(&image(), Format::Svg, 10)).unwrap();
+    assert!(svg.starts_with("<svg "));
+    assert!(svg.contains(r#"width="30" height="20" viewBox="0 0 3 2""#));
+    assert!(svg.trim_end().ends_with("</svg>"));
+
+    // black comes first among the equally common colors, so it is the
+    // background
+    assert!(svg.contains(r##"<rect width="3" height="2" fill="#000000"/>"##));
+    assert!(svg.contains(r##"<rect x="1" y="1" width="1" height="1" fill="#ffffff"/>"##));
+    assert!(svg.contains(r##"<rect x="2" y="0" width="1" height="1" fill="#ff0000"/>"##));
+    assert_eq!(svg.matches("<rect").count(), 5);
+}
+
+#[test]
+fn formats() {
+    assert_eq!(Format::from_path("day8.PNG"), Some(Format::Png));
+    assert_eq!(Format::from_path("out/maze.svg"), Some(Format::Svg));
+    assert_eq!(Format::from_path("maze.pbm"), Some(Format::Pbm));
+    assert_eq!(Format::from_path("maze.ppm"), Some(Format::Ppm));
+    assert_eq!(Format::from_path("maze.bmp"), None);
+    assert_eq!(Format::from_path("maze"), None);
+
+    let err = image().save("maze.bmp", 1).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+    let mut out = Vec::new();
+    assert!(image().write(Format::Png, 0, &mut out).is_err());
+}
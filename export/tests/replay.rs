@@ -0,0 +1,286 @@
+use export::replay::{Options, Recorder};
+use export::{Image, Rgb};
+use grid::Pos;
+use std::time::Duration;
+
+/// A 2x1 image showing `n`.
+fn frame(n: u8) -> Image {
+    let mut image = Image::new(2, 1, Rgb::BLACK);
+    image.set(Pos::new(1, 0), Rgb(n, n, n));
+    image
+}
+
+fn record(options: Options, nb_frames: u8) -> Vec<u8> {
+    let mut recorder = Recorder::new(options);
+    for n in 0..nb_frames - 1 {
+        if recorder.is_full() {
+            break;
+        }
+        recorder.record(|| frame(n));
+    }
+    recorder.finish(|| frame(nb_frames - 1));
+
+    recorder
+        .frames()
+        .iter()
+        .map(|f| f.get(Pos::new(1, 0)).unwrap().0)
+        .collect()
+}
+
+#[test]
+fn skip_frames() {
+    assert_eq!(record(Options::default(), 5), [0, 1, 2, 3, 4]);
+
+    let every = |every| Options {
+        every,
+        max_frames: None,
+    };
+    // the final frame is always kept
+    assert_eq!(record(every(3), 8), [0, 3, 6, 7]);
+    assert_eq!(record(every(3), 7), [0, 3, 6]);
+    assert_eq!(record(every(0), 3), [0, 1, 2]);
+
+    let max = Options {
+        every: 2,
+        max_frames: Some(3),
+    };
+    assert_eq!(record(max, 20), [0, 2, 4]);
+}
+
+/// A GIF file, decoded.
+struct Gif {
+    width: u16,
+    height: u16,
+    delays: Vec<u16>,
+    frames: Vec<Vec<Rgb>>,
+}
+
+fn decode(gif: &[u8]) -> Gif {
+    assert_eq!(&gif[..6], b"GIF89a");
+    let width = u16::from_le_bytes([gif[6], gif[7]]);
+    let height = u16::from_le_bytes([gif[8], gif[9]]);
+    assert_eq!(gif[10] & 0x80, 0x80, "no global color table");
+    let nb_colors = 2 << (gif[10] & 7);
+    let palette: Vec<_> = gif[13..13 + 3 * nb_colors]
+        .chunks(3)
+        .map(|c| Rgb(c[0], c[1], c[2]))
+        .collect();
+
+    let mut pos = 13 + 3 * nb_colors;
+    let mut result = Gif {
+        width,
+        height,
+        delays: Vec::new(),
+        frames: Vec::new(),
+    };
+    let sub_blocks = |pos: &mut usize| {
+        let mut data = Vec::new();
+        while gif[*pos] != 0 {
+            let len = gif[*pos] as usize;
+            data.extend_from_slice(&gif[*pos + 1..*pos + 1 + len]);
+            *pos += len + 1;
+        }
+        *pos += 1;
+        data
+    };
+    loop {
+        match gif[pos] {
+            0x21 => {
+                let label = gif[pos + 1];
+                pos += 2;
+                let data = sub_blocks(&mut pos);
+                if label == 0xf9 {
+                    result.delays.push(u16::from_le_bytes([data[1], data[2]]));
+                }
+            }
+            0x2c => {
+                let w = u16::from_le_bytes([gif[pos + 5], gif[pos + 6]]) as usize;
+                let h = u16::from_le_bytes([gif[pos + 7], gif[pos + 8]]) as usize;
+                let min_code_size = gif[pos + 10];
+                pos += 11;
+                let indices = lzw_decode(&sub_blocks(&mut pos), min_code_size);
+                assert_eq!(indices.len(), w * h);
+                result
+                    .frames
+                    .push(indices.iter().map(|i| palette[*i as usize]).collect());
+            }
+            0x3b => break,
+            b => panic!("unexpected block {:#x}", b),
+        }
+    }
+    assert_eq!(pos + 1, gif.len());
+    result
+}
+
+fn lzw_decode(data: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear = 1usize << min_code_size;
+    let end = clear + 1;
+    let mut bit = 0;
+    let mut read = |size: u8| {
+        let mut code = 0;
+        for i in 0..size as usize {
+            let byte = data[(bit + i) / 8];
+            code |= (((byte >> ((bit + i) % 8)) & 1) as usize) << i;
+        }
+        bit += size as usize;
+        code
+    };
+
+    let mut out = Vec::new();
+    let mut table: Vec<Vec<u8>> = Vec::new();
+    let mut size = min_code_size + 1;
+    let mut prev: Option<Vec<u8>> = None;
+    loop {
+        let code = read(size);
+        if code == clear {
+            table = (0..clear).map(|i| vec![i as u8]).collect();
+            table.push(vec![]);
+            table.push(vec![]);
+            size = min_code_size + 1;
+            prev = None;
+            continue;
+        }
+        if code == end {
+            break;
+        }
+        let entry = match &prev {
+            None => table[code].clone(),
+            Some(p) => {
+                let entry = if code < table.len() {
+                    table[code].clone()
+                } else {
+                    assert_eq!(code, table.len(), "invalid code");
+                    let mut e = p.clone();
+                    e.push(p[0]);
+                    e
+                };
+                let mut new = p.clone();
+                new.push(entry[0]);
+                table.push(new);
+                if table.len() == 1 << size && size < 12 {
+                    size += 1;
+                }
+                entry
+            }
+        };
+        out.extend_from_slice(&entry);
+        prev = Some(entry);
+    }
+    assert_eq!(bit.div_ceil(8), data.len());
+    out
+}
+
+#[test]
+fn gif() {
+    let mut recorder = Recorder::new(Options::default());
+    recorder.record(|| frame(10));
+    recorder.finish(|| frame(20));
+
+    let mut out = Vec::new();
+    recorder
+        .write_gif(3, Duration::from_millis(250), &mut out)
+        .unwrap();
+    let gif = decode(&out);
+    assert_eq!((gif.width, gif.height), (6, 3));
+    assert_eq!(gif.delays, [25, 25]);
+    let gray = Rgb(20, 20, 20);
+    assert_eq!(
+        gif.frames[1][..6],
+        [Rgb::BLACK, Rgb::BLACK, Rgb::BLACK, gray, gray, gray]
+    );
+    assert_eq!(gif.frames[1].len(), 18);
+}
+
+#[test]
+fn gif_compression() {
+    // noise, with every color, fills the table of codes several times
+    let mut noisy = Image::new(256, 128, Rgb::BLACK);
+    let mut seed = 1u32;
+    for y in 0..128 {
+        for x in 0..256 {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            let c = if x < 128 { (seed >> 16) as u8 } else { x as u8 };
+            noisy.set(Pos::new(x, y), Rgb(c, c, 255 - c));
+        }
+    }
+    // and a flat frame compresses to long codes
+    let flat = Image::new(256, 128, Rgb(255, 255, 0));
+
+    let mut recorder = Recorder::new(Options::default());
+    recorder.record(|| noisy.clone());
+    recorder.finish(|| flat.clone());
+    let mut out = Vec::new();
+    recorder
+        .write_gif(1, Duration::default(), &mut out)
+        .unwrap();
+
+    let gif = decode(&out);
+    let pixels = |image: &Image| -> Vec<Rgb> {
+        (0..128)
+            .flat_map(|y| (0..256).map(move |x| Pos::new(x, y)))
+            .map(|p| image.get(p).unwrap())
+            .collect()
+    };
+    assert_eq!(gif.frames[0], pixels(&noisy));
+    assert_eq!(gif.frames[1], pixels(&flat));
+
+    // too many colors
+    let mut image = Image::new(300, 1, Rgb::BLACK);
+    for x in 0..300 {
+        image.set(Pos::new(x, 0), Rgb(x as u8, (x >> 8) as u8, 0));
+    }
+    let mut recorder = Recorder::new(Options::default());
+    recorder.finish(|| image);
+    assert!(recorder
+        .write_gif(1, Duration::default(), Vec::new())
+        .is_err());
+
+    let recorder = Recorder::new(Options::default());
+    assert!(recorder
+        .write_gif(1, Duration::default(), Vec::new())
+        .is_err());
+
+    // scaled size overflowing
+    let mut recorder = Recorder::new(Options::default());
+    recorder.finish(|| frame(0));
+    let error = recorder
+        .write_gif(usize::MAX, Duration::default(), Vec::new())
+        .unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn numbered_frames() {
+    let dir = std::env::temp_dir().join(format!("export-replay-{}", std::process::id()));
+    let mut recorder = Recorder::new(Options::default());
+    for n in 0..10 {
+        recorder.record(|| frame(n));
+    }
+    recorder.finish(|| frame(10));
+
+    let pattern = dir.join("frames").join("step-{}.ppm");
+    let paths = recorder
+        .save(pattern.to_str().unwrap(), 1, Duration::default())
+        .unwrap();
+    assert_eq!(paths.len(), 11);
+    assert_eq!(paths[0].file_name().unwrap(), "step-00.ppm");
+    assert_eq!(paths[10].file_name().unwrap(), "step-10.ppm");
+    assert_eq!(
+        std::fs::read(&paths[7]).unwrap(),
+        b"P6\n2 1\n255\n\0\0\0\x07\x07\x07"
+    );
+
+    let gif = dir.join("gif").join("anim.gif");
+    let paths = recorder
+        .save(gif.to_str().unwrap(), 1, Duration::default())
+        .unwrap();
+    assert_eq!(paths.len(), 1);
+    assert_eq!(paths[0], gif);
+    assert_eq!(decode(&std::fs::read(&gif).unwrap()).frames.len(), 11);
+
+    // frames must be numbered, in a known format
+    assert!(recorder.save_frames("frame.png", 1).is_err());
+    assert!(recorder.save_frames("frame-{}.bmp", 1).is_err());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
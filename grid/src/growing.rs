@@ -0,0 +1,168 @@
+use super::{Pos, Render};
+use std::cmp::{max, min};
+use std::fmt;
+
+/// A grid without bounds, where unwritten tiles have a default value.
+///
+/// The allocated area at least doubles when a tile is written outside of
+/// it, so that exploring a map step by step stays cheap.
+#[derive(Debug, Clone)]
+pub struct GrowingGrid<T> {
+    tiles: Vec<T>,
+    /// Position of the first allocated tile.
+    origin: Pos,
+    width: usize,
+    height: usize,
+    default: T,
+    /// Smallest area containing every written tile.
+    bounds: Option<(Pos, Pos)>,
+}
+
+impl<T: Clone> GrowingGrid<T> {
+    pub fn new(default: T) -> Self {
+        Self {
+            tiles: Vec::new(),
+            origin: Pos::default(),
+            width: 0,
+            height: 0,
+            default,
+            bounds: None,
+        }
+    }
+
+    /// The tile at `pos`, growing the grid if needed.
+    pub fn get_mut(&mut self, pos: Pos) -> &mut T {
+        self.bounds = Some(match self.bounds {
+            None => (pos, pos),
+            Some((lo, hi)) => (
+                Pos::new(min(lo.x, pos.x), min(lo.y, pos.y)),
+                Pos::new(max(hi.x, pos.x), max(hi.y, pos.y)),
+            ),
+        });
+
+        let idx = match self.index_of(pos) {
+            Some(idx) => idx,
+            None => {
+                self.grow(pos);
+                self.index_of(pos).unwrap()
+            }
+        };
+        &mut self.tiles[idx]
+    }
+
+    pub fn set(&mut self, pos: Pos, tile: T) {
+        *self.get_mut(pos) = tile;
+    }
+
+    /// Reallocates the grid so that it contains `pos`.
+    fn grow(&mut self, pos: Pos) {
+        let (origin, width, height) = if self.tiles.is_empty() {
+            (pos, 1, 1)
+        } else {
+            let (x, width) = grow_axis(self.origin.x, self.width, pos.x);
+            let (y, height) = grow_axis(self.origin.y, self.height, pos.y);
+            (Pos::new(x, y), width, height)
+        };
+
+        let mut tiles = vec![self.default.clone(); width * height];
+        let dx = (self.origin.x - origin.x) as usize;
+        let dy = (self.origin.y - origin.y) as usize;
+        for (y, row) in self.tiles.chunks(self.width.max(1)).enumerate() {
+            let start = (y + dy) * width + dx;
+            tiles[start..start + row.len()].clone_from_slice(row);
+        }
+
+        self.tiles = tiles;
+        self.origin = origin;
+        self.width = width;
+        self.height = height;
+    }
+}
+
+/// New start and length of an axis so that it contains `pos`, at least
+/// doubling in the direction of `pos`.
+fn grow_axis(start: i64, len: usize, pos: i64) -> (i64, usize) {
+    let end = start + len as i64;
+    if pos < start {
+        let len = max(len * 2, (end - pos) as usize);
+        (end - len as i64, len)
+    } else if pos >= end {
+        (start, max(len * 2, (pos - start + 1) as usize))
+    } else {
+        (start, len)
+    }
+}
+
+impl<T> GrowingGrid<T> {
+    fn index_of(&self, pos: Pos) -> Option<usize> {
+        let rel = pos - self.origin;
+        if rel.x >= 0
+            && rel.y >= 0
+            && (rel.x as usize) < self.width
+            && (rel.y as usize) < self.height
+        {
+            Some(rel.y as usize * self.width + rel.x as usize)
+        } else {
+            None
+        }
+    }
+
+    /// The tile at `pos`, or the default one if it was never written.
+    pub fn get(&self, pos: Pos) -> &T {
+        match self.index_of(pos) {
+            Some(idx) => &self.tiles[idx],
+            None => &self.default,
+        }
+    }
+
+    /// Top-left and bottom-right corners of the smallest area containing
+    /// every written tile.
+    pub fn bounds(&self) -> Option<(Pos, Pos)> {
+        self.bounds
+    }
+
+    /// Positions inside [`bounds`](Self::bounds), row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let (lo, hi) = self.bounds.unwrap_or((Pos::new(0, 0), Pos::new(-1, -1)));
+        (lo.y..=hi.y).flat_map(move |y| (lo.x..=hi.x).map(move |x| Pos::new(x, y)))
+    }
+
+    /// All tiles inside [`bounds`](Self::bounds) with their position,
+    /// row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().map(move |pos| (pos, self.get(pos)))
+    }
+
+    /// Renders the area inside [`bounds`](Self::bounds), mapping every
+    /// tile to a character with `tile`.
+    pub fn render<'a, F>(&'a self, tile: F) -> impl fmt::Display + 'a
+    where
+        F: Fn(&T) -> char + 'a,
+    {
+        Render(move |f: &mut fmt::Formatter<'_>| {
+            if let Some((lo, hi)) = self.bounds {
+                for y in lo.y..=hi.y {
+                    for x in lo.x..=hi.x {
+                        write!(f, "{}", tile(self.get(Pos::new(x, y))))?;
+                    }
+                    writeln!(f)?;
+                }
+            }
+            Ok(())
+        })
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for GrowingGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some((lo, hi)) = self.bounds {
+            for y in lo.y..=hi.y {
+                for x in lo.x..=hi.x {
+                    write!(f, "{}", self.get(Pos::new(x, y)))?;
+                }
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,272 @@
+//! Two dimensional grids of tiles.
+//!
+//! [`Grid`] has fixed bounds starting at the origin, while [`GrowingGrid`]
+//! extends itself as tiles are written, in any direction.
+//!
+//! The `y` axis grows downwards, as in the rendered grid.
+use std::fmt;
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Sub};
+
+mod growing;
+pub use growing::GrowingGrid;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Pos {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Pos {
+    pub const UP: Pos = Pos { x: 0, y: -1 };
+    pub const DOWN: Pos = Pos { x: 0, y: 1 };
+    pub const LEFT: Pos = Pos { x: -1, y: 0 };
+    pub const RIGHT: Pos = Pos { x: 1, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// The 4 orthogonally adjacent positions.
+    pub fn neighbors4(self) -> [Pos; 4] {
+        [
+            self + Pos::UP,
+            self + Pos::RIGHT,
+            self + Pos::DOWN,
+            self + Pos::LEFT,
+        ]
+    }
+
+    /// The 8 adjacent positions, including diagonals.
+    pub fn neighbors8(self) -> [Pos; 8] {
+        let [up, right, down, left] = self.neighbors4();
+        [
+            up,
+            up + Pos::RIGHT,
+            right,
+            down + Pos::RIGHT,
+            down,
+            down + Pos::LEFT,
+            left,
+            up + Pos::LEFT,
+        ]
+    }
+
+    pub fn manhattan(self, other: Pos) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+}
+
+impl Add for Pos {
+    type Output = Pos;
+
+    fn add(self, other: Pos) -> Pos {
+        Pos::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Pos {
+    fn add_assign(&mut self, other: Pos) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Pos {
+    type Output = Pos;
+
+    fn sub(self, other: Pos) -> Pos {
+        Pos::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<i64> for Pos {
+    type Output = Pos;
+
+    fn mul(self, factor: i64) -> Pos {
+        Pos::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl fmt::Display for Pos {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+/// A grid covering `0..width` and `0..height`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    tiles: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, tile: T) -> Self {
+        Self {
+            tiles: vec![tile; width * height],
+            width,
+            height,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Parses ASCII art, one row per line, mapping every character with
+    /// `tile`. Rows must have the same length.
+    pub fn parse<F>(input: &str, mut tile: F) -> puzzle::Result<Self>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut tiles = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+
+        for (idx, line) in input.lines().enumerate() {
+            let error = |column, message| puzzle::Error {
+                line: idx + 1,
+                column,
+                message,
+            };
+            if line.is_empty() {
+                // only trailing empty lines are allowed
+                if input.lines().skip(idx).any(|l| !l.is_empty()) {
+                    return Err(error(1, "unexpected empty line".to_owned()));
+                }
+                break;
+            }
+
+            let mut len = 0;
+            for c in line.chars() {
+                len += 1;
+                tiles.push(tile(c).ok_or_else(|| error(len, format!("invalid tile {:?}", c)))?);
+            }
+            match width {
+                None => width = Some(len),
+                Some(w) if w != len => {
+                    return Err(error(
+                        len + 1,
+                        format!("expected {} tiles, found {}", w, len),
+                    ));
+                }
+                Some(_) => (),
+            }
+            height += 1;
+        }
+
+        Ok(Self {
+            tiles,
+            width: width.unwrap_or(0),
+            height,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.x >= 0 && pos.y >= 0 && (pos.x as usize) < self.width && (pos.y as usize) < self.height
+    }
+
+    fn index_of(&self, pos: Pos) -> Option<usize> {
+        if self.contains(pos) {
+            Some(pos.y as usize * self.width + pos.x as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.index_of(pos).map(|idx| &self.tiles[idx])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.index_of(pos).map(move |idx| &mut self.tiles[idx])
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let (width, height) = (self.width as i64, self.height as i64);
+        (0..height).flat_map(move |y| (0..width).map(move |x| Pos::new(x, y)))
+    }
+
+    /// All tiles with their position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.tiles.iter())
+    }
+
+    /// Orthogonally adjacent positions inside the grid.
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        // by value, as arrays only iterate by reference in edition 2018
+        IntoIterator::into_iter(pos.neighbors4()).filter(move |p| self.contains(*p))
+    }
+
+    /// Adjacent positions inside the grid, including diagonals.
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        IntoIterator::into_iter(pos.neighbors8()).filter(move |p| self.contains(*p))
+    }
+
+    /// Renders the grid, mapping every tile to a character with `tile`.
+    pub fn render<'a, F>(&'a self, tile: F) -> impl fmt::Display + 'a
+    where
+        F: Fn(&T) -> char + 'a,
+    {
+        Render(move |f: &mut fmt::Formatter<'_>| {
+            for row in self.tiles.chunks(self.width.max(1)) {
+                for t in row {
+                    write!(f, "{}", tile(t))?;
+                }
+                writeln!(f)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        match self.get(pos) {
+            Some(t) => t,
+            None => panic!("position {} out of the grid", pos),
+        }
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        match self.index_of(pos) {
+            Some(idx) => &mut self.tiles[idx],
+            None => panic!("position {} out of the grid", pos),
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.tiles.chunks(self.width.max(1)) {
+            for t in row {
+                write!(f, "{}", t)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Adapts a formatting closure to [`fmt::Display`].
+struct Render<F>(F);
+
+impl<F> fmt::Display for Render<F>
+where
+    F: Fn(&mut fmt::Formatter<'_>) -> fmt::Result,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (self.0)(f)
+    }
+}
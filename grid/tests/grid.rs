@@ -0,0 +1,116 @@
+use grid::{Grid, GrowingGrid, Pos};
+
+const MAP: &str = "\
+#.#
+...
+..#
+";
+
+fn parse(input: &str) -> puzzle::Result<Grid<bool>> {
+    Grid::parse(input, |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
+}
+
+#[test]
+fn parse_and_render() {
+    let grid = parse(MAP).unwrap();
+    assert_eq!((grid.width(), grid.height()), (3, 3));
+    assert!(grid[Pos::new(2, 2)]);
+    assert!(!grid[Pos::new(1, 0)]);
+    assert_eq!(grid.iter().filter(|(_, t)| **t).count(), 3);
+
+    let rendered = grid.render(|t| if *t { '#' } else { '.' }).to_string();
+    assert_eq!(rendered, MAP);
+}
+
+#[test]
+fn parse_errors() {
+    let err = parse("#.#\n.x.\n").unwrap_err();
+    assert_eq!(err.to_string(), "2:2: invalid tile 'x'");
+
+    let err = parse("#.#\n..\n").unwrap_err();
+    assert_eq!(err.to_string(), "2:3: expected 3 tiles, found 2");
+
+    let err = parse("#.#\n\n...\n").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 1));
+
+    // trailing empty lines are fine
+    assert_eq!(parse("#.\n..\n\n\n").unwrap().height(), 2);
+}
+
+#[test]
+fn bounds_checked_access() {
+    let mut grid = Grid::new(4, 2, 0);
+    assert!(grid.get(Pos::new(4, 0)).is_none());
+    assert!(grid.get(Pos::new(0, -1)).is_none());
+    assert!(grid.get_mut(Pos::new(0, 2)).is_none());
+
+    grid[Pos::new(3, 1)] = 7;
+    assert_eq!(grid.get(Pos::new(3, 1)), Some(&7));
+    assert_eq!(grid.to_string(), "0000\n0007\n");
+}
+
+#[test]
+#[should_panic(expected = "position 4,0 out of the grid")]
+fn index_out_of_bounds() {
+    let grid = Grid::new(4, 2, 0);
+    let _ = grid[Pos::new(4, 0)];
+}
+
+#[test]
+fn neighbors() {
+    let grid = Grid::new(3, 3, ());
+    let center = Pos::new(1, 1);
+    assert_eq!(grid.neighbors4(center).count(), 4);
+    assert_eq!(grid.neighbors8(center).count(), 8);
+
+    let corner = Pos::new(0, 0);
+    let mut n4: Vec<_> = grid.neighbors4(corner).collect();
+    n4.sort();
+    assert_eq!(n4, [Pos::new(0, 1), Pos::new(1, 0)]);
+    assert_eq!(grid.neighbors8(corner).count(), 3);
+
+    for n in center.neighbors8().iter() {
+        assert_eq!((*n - center).x.abs().max((*n - center).y.abs()), 1);
+    }
+}
+
+#[test]
+fn growing_grid() {
+    let mut grid = GrowingGrid::new('.');
+    assert_eq!(grid.bounds(), None);
+    assert_eq!(grid.to_string(), "");
+
+    let mut pos = Pos::new(0, 0);
+    grid.set(pos, 'S');
+    for _ in 0..20 {
+        pos += Pos::LEFT + Pos::UP;
+        grid.set(pos, '#');
+    }
+    grid.set(Pos::new(3, 1), 'E');
+
+    assert_eq!(grid.bounds(), Some((Pos::new(-20, -20), Pos::new(3, 1))));
+    assert_eq!(*grid.get(Pos::new(-20, -20)), '#');
+    assert_eq!(*grid.get(Pos::new(0, 0)), 'S');
+    assert_eq!(*grid.get(Pos::new(3, 1)), 'E');
+    assert_eq!(*grid.get(Pos::new(100, -100)), '.');
+    assert_eq!(grid.iter().filter(|(_, t)| **t == '#').count(), 20);
+
+    let rendered = grid.to_string();
+    let lines: Vec<_> = rendered.lines().collect();
+    assert_eq!(lines.len(), 22);
+    assert_eq!(lines[0], format!("#{}", ".".repeat(23)));
+    assert_eq!(lines[20], format!("{}S...", ".".repeat(20)));
+    assert_eq!(lines[21], format!("{}E", ".".repeat(23)));
+
+    let rendered = grid
+        .render(|t| if *t == '.' { ' ' } else { 'X' })
+        .to_string();
+    assert_eq!(
+        rendered.lines().nth(21),
+        Some(&*format!("{}X", " ".repeat(23)))
+    );
+}
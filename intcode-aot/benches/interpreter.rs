@@ -0,0 +1,90 @@
+//! Compare translated programs with the interpreter.
+//!
+//! Run with `cargo bench`.
+
+use intcode::Intcode;
+use intcode_aot::{day2, day9};
+use std::fs;
+use std::time::{Duration, Instant};
+
+fn load(path: &str) -> Vec<i64> {
+    fs::read_to_string(path)
+        .unwrap()
+        .split(',')
+        .map(|v| v.trim().parse().unwrap())
+        .collect()
+}
+
+/// Best time out of `iterations` runs of `f`.
+fn measure<F: FnMut() -> i64>(iterations: usize, mut f: F) -> (Duration, i64) {
+    let mut best = None;
+    let mut result = 0;
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+        result = f();
+        let elapsed = start.elapsed();
+        best = Some(best.map_or(elapsed, |b: Duration| b.min(elapsed)));
+    }
+    (best.unwrap(), result)
+}
+
+fn compare<F, G>(name: &str, iterations: usize, interpreted: F, translated: G)
+where
+    F: FnMut() -> i64,
+    G: FnMut() -> i64,
+{
+    let (t1, r1) = measure(iterations, interpreted);
+    let (t2, r2) = measure(iterations, translated);
+    assert_eq!(r1, r2);
+
+    println!(
+        "{:<12} interpreter {:>10.3?}  translated {:>10.3?}  speedup {:.2}x",
+        name,
+        t1,
+        t2,
+        t1.as_secs_f64() / t2.as_secs_f64()
+    );
+}
+
+fn main() {
+    let day2_memory = load("../day2/input.txt");
+    compare(
+        "day2 sweep",
+        10,
+        || {
+            let mut sum = 0;
+            for noun in 0..100 {
+                for verb in 0..100 {
+                    let mut intcode = Intcode::new(&day2_memory);
+                    intcode.memory[1] = noun;
+                    intcode.memory[2] = verb;
+                    intcode.run(&[]);
+                    sum += intcode.memory[0] % 1000;
+                }
+            }
+            sum
+        },
+        || {
+            let mut sum = 0;
+            for noun in 0..100 {
+                for verb in 0..100 {
+                    let mut program = day2::Program::new();
+                    program.memory_mut()[1] = noun;
+                    program.memory_mut()[2] = verb;
+                    program.run(&[]);
+                    sum += program.memory()[0] % 1000;
+                }
+            }
+            sum
+        },
+    );
+
+    let day9_memory = load("../day9/input.txt");
+    compare(
+        "day9 boost",
+        10,
+        || Intcode::new(&day9_memory).run(&[2])[0],
+        || day9::Program::new().run(&[2])[0],
+    );
+}
@@ -0,0 +1,38 @@
+//! Translate the programs used by the tests and benchmarks.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+/// Module name, program file, dynamic cells.
+const PROGRAMS: &[(&str, &str, &[usize])] = &[
+    // noun and verb are patched before running
+    ("day2", "../day2/input.txt", &[1, 2]),
+    ("day5", "../day5/input.txt", &[]),
+    ("day7", "../day7/input.txt", &[]),
+    ("day9", "../day9/input.txt", &[]),
+    ("echo", "programs/echo.txt", &[]),
+    ("selfmod", "programs/selfmod.txt", &[]),
+    ("errors", "programs/errors.txt", &[]),
+    ("partial", "programs/partial.txt", &[]),
+    // the output parameter is past the end of the program
+    ("short", "programs/short.txt", &[1]),
+];
+
+fn main() -> Result<()> {
+    let out_dir = env::var("OUT_DIR")?;
+
+    for (name, path, dynamic) in PROGRAMS {
+        println!("cargo:rerun-if-changed={}", path);
+
+        let mut memory = Vec::new();
+        for v in fs::read_to_string(path)?.split(',') {
+            memory.push(v.trim().parse::<i64>()?);
+        }
+        let module = intcode::aot::translate(&memory, dynamic);
+        fs::write(Path::new(&out_dir).join(format!("{}.rs", name)), module)?;
+    }
+    Ok(())
+}
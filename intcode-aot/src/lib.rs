@@ -0,0 +1,15 @@
+//! Intcode programs translated to Rust with `intcode::aot` at build
+//! time, see `build.rs`.
+
+macro_rules! translated {
+    ($($name:ident),*) => {
+        $(
+            #[allow(clippy::all)]
+            pub mod $name {
+                include!(concat!(env!("OUT_DIR"), "/", stringify!($name), ".rs"));
+            }
+        )*
+    };
+}
+
+translated!(day2, day5, day7, day9, echo, selfmod, errors, partial, short);
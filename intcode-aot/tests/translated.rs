@@ -0,0 +1,136 @@
+use intcode::{Error, Intcode};
+use intcode_aot::{day2, day5, day7, day9, echo, errors, partial, selfmod, short};
+use std::fs;
+
+fn load(path: &str) -> Vec<i64> {
+    fs::read_to_string(path)
+        .unwrap()
+        .split(',')
+        .map(|v| v.trim().parse().unwrap())
+        .collect()
+}
+
+#[test]
+fn day2_patched() {
+    let memory = load("../day2/input.txt");
+
+    for (noun, verb) in &[(12, 2), (0, 0), (99, 99), (53, 7)] {
+        let mut intcode = Intcode::new(&memory);
+        intcode.memory[1] = *noun;
+        intcode.memory[2] = *verb;
+        let expected = intcode.try_run(&[]);
+
+        let mut program = day2::Program::new();
+        program.memory_mut()[1] = *noun;
+        program.memory_mut()[2] = *verb;
+        assert_eq!(program.try_run(&[]), expected);
+        assert_eq!(program.memory()[0], intcode.memory[0]);
+        assert!(program.is_done());
+        assert!(!program.is_interpreted());
+    }
+}
+
+#[test]
+fn day5() {
+    let memory = load("../day5/input.txt");
+
+    // the program patches its own opcodes, so it ends up interpreted
+    for input in &[1, 5] {
+        let expected = Intcode::new(&memory).run(&[*input]);
+        let mut program = day5::Program::new();
+        assert_eq!(program.run(&[*input]), expected);
+    }
+}
+
+#[test]
+fn day9() {
+    let memory = load("../day9/input.txt");
+
+    for input in &[1, 2] {
+        let expected = Intcode::new(&memory).run(&[*input]);
+        let mut program = day9::Program::new();
+        assert_eq!(program.run(&[*input]), expected);
+        assert!(program.is_done());
+        assert!(!program.is_interpreted());
+    }
+}
+
+#[test]
+fn resume() {
+    let mut program = echo::Program::new();
+    assert_eq!(program.run(&[]), Vec::<i64>::new());
+    assert_eq!(program.run(&[1, 2]), vec![2, 3]);
+    assert_eq!(program.run(&[5]), vec![6]);
+    assert!(!program.is_done());
+    assert!(!program.is_interpreted());
+}
+
+/// Feedback loop of day 7, checking every exchange. The program jumps
+/// through a table indexed by the phase setting, which is only handled
+/// by the interpreter.
+#[test]
+fn day7_feedback_loop() {
+    let memory = load("../day7/input.txt");
+
+    for phases in &[[9, 8, 7, 6, 5], [5, 7, 9, 6, 8]] {
+        let mut amps: Vec<_> = phases
+            .iter()
+            .map(|phase| {
+                let mut intcode = Intcode::new(&memory);
+                let mut program = day7::Program::new();
+                assert_eq!(program.run(&[*phase]), intcode.run(&[*phase]));
+                (intcode, program)
+            })
+            .collect();
+
+        let mut signal = vec![0];
+        while !amps[4].0.is_done() {
+            for (intcode, program) in amps.iter_mut() {
+                let expected = intcode.run(&signal);
+                signal = program.run(&signal);
+                assert_eq!(signal, expected);
+                assert_eq!(program.is_done(), intcode.is_done());
+                assert!(program.is_interpreted());
+            }
+        }
+    }
+}
+
+#[test]
+fn write_into_code() {
+    let mut program = selfmod::Program::new();
+    assert_eq!(program.run(&[]), Vec::<i64>::new());
+    assert!(program.is_done());
+    assert!(program.is_interpreted());
+    assert_eq!(program.memory()[4], 99);
+}
+
+#[test]
+fn errors() {
+    let mut program = errors::Program::new();
+    assert_eq!(program.try_run(&[]), Err(Error::InvalidAddress(-5)));
+    assert_eq!(program.memory()[20], 3);
+}
+
+#[test]
+fn outputs_before_error() {
+    let mut program = partial::Program::new();
+    assert_eq!(program.try_run(&[]), Err(Error::InvalidAddress(-1)));
+    assert_eq!(program.take_pending_outputs(), vec![1, 2]);
+    assert_eq!(program.take_pending_outputs(), Vec::<i64>::new());
+
+    // same when the error is raised by the interpreter
+    let mut program = short::Program::new();
+    assert_eq!(program.try_run(&[]), Err(Error::UnknownOpcode(0)));
+    assert!(program.is_interpreted());
+    assert_eq!(program.take_pending_outputs(), vec![0]);
+}
+
+#[test]
+fn dynamic_past_image() {
+    let mut program = short::Program::new();
+    program.memory_mut().push(7);
+    assert!(program.try_run(&[]).is_err());
+    assert_eq!(program.take_pending_outputs(), vec![7]);
+    assert_eq!(program.memory()[..2], [104, 7]);
+}
@@ -0,0 +1,36 @@
+//! Translate an Intcode program to a Rust module.
+//!
+//! Usage: `cargo run --example aot -- input.txt [dynamic cells] > program.rs`,
+//! dynamic cells being a comma separated list of addresses.
+
+use std::env;
+use std::fs;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+fn parse_values<T: std::str::FromStr>(s: &str) -> Result<Vec<T>>
+where
+    T::Err: std::error::Error + 'static,
+{
+    let mut values = Vec::new();
+    for v in s.split(',') {
+        values.push(v.trim().parse::<T>()?);
+    }
+    Ok(values)
+}
+
+fn main() -> Result<()> {
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 2 {
+        return Err("usage: aot <program> [dynamic cells]".into());
+    }
+
+    let memory: Vec<i64> = parse_values(&fs::read_to_string(&args[1])?)?;
+    let dynamic: Vec<usize> = match args.get(2) {
+        Some(cells) => parse_values(cells)?,
+        None => Vec::new(),
+    };
+
+    print!("{}", intcode::aot::translate(&memory, &dynamic));
+    Ok(())
+}
@@ -0,0 +1,42 @@
+//! Serve an Intcode program to GDB.
+//!
+//! Usage: `cargo run --example gdbserver -- input.txt [port] [inputs]`,
+//! then `target remote localhost:<port>` in GDB.
+
+use intcode::gdb::Server;
+use intcode::Intcode;
+use std::env;
+use std::fs;
+use std::net::TcpListener;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+fn parse_values(s: &str) -> Result<Vec<i64>> {
+    let mut values = Vec::new();
+    for v in s.split(',') {
+        values.push(v.trim().parse::<i64>()?);
+    }
+    Ok(values)
+}
+
+fn main() -> Result<()> {
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 2 {
+        return Err("usage: gdbserver <program> [port] [inputs]".into());
+    }
+
+    let memory = parse_values(&fs::read_to_string(&args[1])?)?;
+    let port = args.get(2).map(|p| p.as_str()).unwrap_or("1234");
+    let inputs = match args.get(3) {
+        Some(inputs) => parse_values(inputs)?,
+        None => Vec::new(),
+    };
+
+    let listener = TcpListener::bind(format!("127.0.0.1:{}", port))?;
+    println!("listening on {}", listener.local_addr()?);
+
+    let mut server = Server::new(Intcode::new(&memory), &inputs);
+    server.serve(&listener)?;
+    println!("outputs: {:?}", server.outputs());
+    Ok(())
+}
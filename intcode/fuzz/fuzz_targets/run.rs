@@ -0,0 +1,34 @@
+//! Feed arbitrary memory images to the VM: `try_run` must return an
+//! error rather than panic.
+//!
+//! Run with `cargo fuzz run run`. Limits are set so that programs
+//! looping forever or addressing huge amounts of memory stop quickly.
+#![no_main]
+use intcode::{Intcode, Limits};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let words: Vec<i64> = data
+        .chunks_exact(8)
+        .map(|c| {
+            let mut bytes = [0; 8];
+            bytes.copy_from_slice(c);
+            i64::from_le_bytes(bytes)
+        })
+        .collect();
+
+    // the first word gives the number of inputs, taken from the end
+    let nb_inputs = match words.first() {
+        Some(n) => (*n as usize) % (words.len() + 1),
+        None => return,
+    };
+    let (memory, inputs) = words.split_at(words.len() - nb_inputs);
+
+    let mut intcode = Intcode::new(memory);
+    intcode.set_limits(Limits {
+        max_instructions: Some(100_000),
+        max_memory: Some(1 << 20),
+        ..Limits::default()
+    });
+    let _ = intcode.try_run(inputs);
+});
@@ -0,0 +1,635 @@
+//! Ahead-of-time translation of Intcode programs to Rust.
+//!
+//! `translate` turns a memory image into a Rust module defining a
+//! `Program` type, with the same interface as `Intcode` (`run`,
+//! `try_run`, `is_done`). The code reachable from address 0 is split
+//! into basic blocks, each translated into a function, and a dispatch
+//! loop jumps from one block to the other.
+//!
+//! The translation assumes the code is not modified. Before running a
+//! block whose code was written to, or when the program jumps to an
+//! address that was not identified as a block, execution falls back to
+//! the interpreter, from the current state. Writes to code that is not
+//! executed afterwards, such as arguments of already executed
+//! instructions, are thus fine.
+//!
+//! Cells listed as dynamic are read from memory when executed instead
+//! of being embedded in the translation, so that they can be patched
+//! before running, like the noun and verb of day 2. As in the
+//! interpreter, reading them past the end of the memory grows it.
+
+use std::collections::{BTreeSet, HashSet};
+use std::fmt::Write;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Raw {
+    Const(i64),
+    /// Value read from this cell at runtime.
+    Dynamic(usize),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Operand {
+    mode: i64,
+    raw: Raw,
+}
+
+struct Instr {
+    addr: usize,
+    opcode: i64,
+    operands: Vec<Operand>,
+}
+
+impl Instr {
+    fn next(&self) -> usize {
+        self.addr + 1 + self.operands.len()
+    }
+}
+
+struct Image<'a> {
+    memory: &'a [i64],
+    dynamic: HashSet<usize>,
+}
+
+impl<'a> Image<'a> {
+    fn raw(&self, addr: usize) -> Raw {
+        if self.dynamic.contains(&addr) {
+            Raw::Dynamic(addr)
+        } else {
+            Raw::Const(*self.memory.get(addr).unwrap_or(&0))
+        }
+    }
+
+    /// Decode the instruction at `addr`. Returns `None` if it is not a
+    /// valid instruction, or if it depends on dynamic cells in a way
+    /// that cannot be translated.
+    fn decode(&self, addr: usize) -> Option<Instr> {
+        let instruction = match self.raw(addr) {
+            Raw::Const(v) => v,
+            Raw::Dynamic(_) => return None,
+        };
+        if instruction < 0 {
+            return None;
+        }
+        let opcode = instruction % 100;
+
+        // kind of each parameter: true for write targets
+        let params: &[bool] = match opcode {
+            1 | 2 | 7 | 8 => &[false, false, true],
+            3 => &[true],
+            4 | 9 => &[false],
+            5 | 6 => &[false, false],
+            99 => &[],
+            _ => return None,
+        };
+
+        let mut modes = instruction / 100;
+        let mut operands = Vec::new();
+        for (idx, is_write) in params.iter().enumerate() {
+            let mode = modes % 10;
+            modes /= 10;
+            match mode {
+                0 | 2 => (),
+                1 if !is_write => (),
+                _ => return None,
+            }
+            operands.push(Operand {
+                mode,
+                raw: self.raw(addr + 1 + idx),
+            });
+        }
+
+        Some(Instr {
+            addr,
+            opcode,
+            operands,
+        })
+    }
+}
+
+/// Addresses at which a block must start, and cells holding code.
+struct Analysis {
+    leaders: BTreeSet<usize>,
+    code: HashSet<usize>,
+}
+
+fn jump_target(instr: &Instr) -> Option<usize> {
+    match instr.operands[1] {
+        Operand {
+            mode: 1,
+            raw: Raw::Const(v),
+        } if v >= 0 => Some(v as usize),
+        _ => None,
+    }
+}
+
+fn analyze(image: &Image) -> Analysis {
+    let mut leaders = BTreeSet::new();
+    let mut code = HashSet::new();
+    let mut visited = HashSet::new();
+    let mut todo = vec![0];
+
+    leaders.insert(0);
+    while let Some(mut addr) = todo.pop() {
+        while visited.insert(addr) {
+            let instr = match image.decode(addr) {
+                Some(instr) => instr,
+                None => break,
+            };
+            // dynamic cells are always read from memory, writing to
+            // them is fine
+            code.extend((instr.addr..instr.next()).filter(|a| !image.dynamic.contains(a)));
+
+            match instr.opcode {
+                // resuming after a lack of input restarts here
+                3 => {
+                    leaders.insert(addr);
+                }
+                5 | 6 => {
+                    if let Some(target) = jump_target(&instr) {
+                        leaders.insert(target);
+                        todo.push(target);
+                    }
+                    leaders.insert(instr.next());
+                    todo.push(instr.next());
+                    break;
+                }
+                99 => break,
+                _ => (),
+            }
+            addr = instr.next();
+        }
+    }
+
+    Analysis { leaders, code }
+}
+
+// {{{ Code generation
+
+struct Gen<'a> {
+    image: &'a Image<'a>,
+    analysis: &'a Analysis,
+    out: String,
+    /// End of the block being generated.
+    end: usize,
+}
+
+impl<'a> Gen<'a> {
+    fn raw(&self, raw: Raw) -> String {
+        match raw {
+            Raw::Const(v) => format!("{}i64", v),
+            Raw::Dynamic(addr) => format!("d{}", addr),
+        }
+    }
+
+    /// Expression giving the address designated by a write operand, or
+    /// by a read operand in position or relative mode.
+    fn address(&self, op: &Operand) -> String {
+        match op.mode {
+            2 => format!("self.rel({})?", self.raw(op.raw)),
+            _ => self.raw(op.raw),
+        }
+    }
+
+    fn read(&self, op: &Operand) -> String {
+        match op.mode {
+            1 => self.raw(op.raw),
+            _ => format!("self.rd({})?", self.address(op)),
+        }
+    }
+
+    /// Is a write through `op` known not to land in the code?
+    fn safe_write(&self, op: &Operand) -> bool {
+        match (op.mode, op.raw) {
+            (0, Raw::Const(v)) => v < 0 || !self.analysis.code.contains(&(v as usize)),
+            _ => false,
+        }
+    }
+
+    fn write(&mut self, instr: &Instr, op: &Operand, value: &str) {
+        let addr = self.address(op);
+        if self.safe_write(op) || instr.next() == self.end {
+            writeln!(self.out, "        self.wr({}, {})?;", addr, value).unwrap();
+        } else {
+            // fall back if the rest of the block was modified
+            writeln!(self.out, "        let dst = {};", addr).unwrap();
+            writeln!(
+                self.out,
+                "        if self.wr(dst, {})? && ({}..{}).contains(&(dst as usize)) {{",
+                value,
+                instr.next(),
+                self.end
+            )
+            .unwrap();
+            writeln!(self.out, "            self.eip = {};", instr.next()).unwrap();
+            writeln!(self.out, "            return Ok(Flow::Fallback);").unwrap();
+            writeln!(self.out, "        }}").unwrap();
+        }
+    }
+
+    fn line(&mut self, s: String) {
+        self.out.push_str("        ");
+        self.out.push_str(&s);
+        self.out.push('\n');
+    }
+
+    fn instr(&mut self, instr: &Instr) {
+        let ops = &instr.operands;
+
+        // dynamic cells are loaded first, as the memory cannot be
+        // borrowed while growing it
+        for op in ops {
+            if let Raw::Dynamic(addr) = op.raw {
+                self.line(format!("let d{} = self.rd({})?;", addr, addr));
+            }
+        }
+
+        match instr.opcode {
+            1 | 2 | 7 | 8 => {
+                self.line(format!("let a = {};", self.read(&ops[0])));
+                self.line(format!("let b = {};", self.read(&ops[1])));
+                let value = match instr.opcode {
+                    1 => "a.checked_add(b).ok_or(Error::Overflow)?",
+                    2 => "a.checked_mul(b).ok_or(Error::Overflow)?",
+                    7 => "if a < b { 1 } else { 0 }",
+                    _ => "if a == b { 1 } else { 0 }",
+                };
+                self.write(instr, &ops[2], value);
+            }
+            3 => {
+                self.line("let v = match io.inputs.get(io.pos) {".to_owned());
+                self.line("    Some(v) => *v,".to_owned());
+                self.line("    None => {".to_owned());
+                self.line(format!("        self.eip = {};", instr.addr));
+                self.line("        return Ok(Flow::Stop);".to_owned());
+                self.line("    }".to_owned());
+                self.line("};".to_owned());
+                self.line("io.pos += 1;".to_owned());
+                self.write(instr, &ops[0], "v");
+            }
+            4 => {
+                self.line(format!("let v = {};", self.read(&ops[0])));
+                self.line("io.output.push(v);".to_owned());
+            }
+            5 | 6 => {
+                let cond = if instr.opcode == 5 { "!=" } else { "==" };
+                self.line(format!("if {} {} 0 {{", self.read(&ops[0]), cond));
+                match jump_target(instr) {
+                    Some(target) => self.line(format!("    self.eip = {};", target)),
+                    None => self.line(format!("    self.eip = addr({})?;", self.read(&ops[1]))),
+                }
+                self.line("    return Ok(Flow::Next);".to_owned());
+                self.line("}".to_owned());
+                self.line(format!("self.eip = {};", instr.next()));
+                self.line("Ok(Flow::Next)".to_owned());
+            }
+            9 => {
+                self.line(format!("let v = {};", self.read(&ops[0])));
+                self.line("self.rel_base = self.rel(v)?;".to_owned());
+            }
+            _ => {
+                self.line("self.is_done = true;".to_owned());
+                self.line(format!("self.eip = {};", instr.next()));
+                self.line("Ok(Flow::Stop)".to_owned());
+            }
+        }
+    }
+
+    /// Instructions of the block starting at `leader`, and whether it
+    /// ends on an invalid instruction.
+    fn decode_block(&self, leader: usize) -> (Vec<Instr>, bool) {
+        let mut instrs = Vec::new();
+        let mut addr = leader;
+        loop {
+            let instr = match self.image.decode(addr) {
+                Some(instr) => instr,
+                None => return (instrs, true),
+            };
+            addr = instr.next();
+            let last = matches!(instr.opcode, 5 | 6 | 99);
+            instrs.push(instr);
+            if last || self.analysis.leaders.contains(&addr) {
+                return (instrs, false);
+            }
+        }
+    }
+
+    fn block_end(&self, leader: usize) -> usize {
+        let (instrs, _) = self.decode_block(leader);
+        instrs.last().map_or(leader, |i| i.next())
+    }
+
+    fn block(&mut self, leader: usize) {
+        let (instrs, trap) = self.decode_block(leader);
+        let uses_io = instrs.iter().any(|i| i.opcode == 3 || i.opcode == 4);
+        self.end = instrs.last().map_or(leader, |i| i.next());
+
+        writeln!(self.out).unwrap();
+        writeln!(
+            self.out,
+            "    fn b{}(&mut self, {}: &mut Io) -> Result<Flow> {{",
+            leader,
+            if uses_io { "io" } else { "_io" }
+        )
+        .unwrap();
+
+        for instr in &instrs {
+            writeln!(
+                self.out,
+                "        // {}: {}",
+                instr.addr,
+                (instr.addr..instr.next())
+                    .map(|a| match self.image.raw(a) {
+                        Raw::Const(v) => v.to_string(),
+                        Raw::Dynamic(_) => "?".to_owned(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(",")
+            )
+            .unwrap();
+            self.instr(instr);
+        }
+
+        let last = instrs.last().map(|i| i.opcode);
+        if trap {
+            // let the interpreter deal with it
+            writeln!(self.out, "        self.eip = {};", self.end).unwrap();
+            writeln!(self.out, "        Ok(Flow::Fallback)").unwrap();
+        } else if !matches!(last, Some(5) | Some(6) | Some(99)) {
+            writeln!(self.out, "        self.eip = {};", self.end).unwrap();
+            writeln!(self.out, "        Ok(Flow::Next)").unwrap();
+        }
+        writeln!(self.out, "    }}").unwrap();
+    }
+}
+
+const PRELUDE: &str = r#"// Generated by intcode::aot, do not edit.
+
+use intcode::{Error, Intcode, Result};
+
+#[allow(dead_code)]
+struct Io<'a> {
+    inputs: &'a [i64],
+    pos: usize,
+    output: Vec<i64>,
+}
+
+#[allow(dead_code)]
+enum Flow {
+    /// Continue with the block at `eip`.
+    Next,
+    /// Halted, or waiting for an input.
+    Stop,
+    /// Continue with the interpreter at `eip`.
+    Fallback,
+}
+
+#[allow(dead_code)]
+fn addr(v: i64) -> Result<usize> {
+    if v < 0 {
+        Err(Error::InvalidAddress(v))
+    } else {
+        Ok(v as usize)
+    }
+}
+
+#[allow(dead_code)]
+fn is_code(addr: usize) -> bool {
+    addr < CODE.len() && CODE[addr]
+}
+
+#[derive(Clone)]
+pub struct Program {
+    memory: Vec<i64>,
+    eip: usize,
+    rel_base: i64,
+    is_done: bool,
+    /// Whether the code was written to.
+    code_written: bool,
+    fallback: Option<Intcode>,
+    /// Outputs of a run interrupted by an error, not yet returned.
+    pending_outputs: Vec<i64>,
+}
+
+impl Default for Program {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Program {
+    pub fn new() -> Self {
+        Self {
+            memory: IMAGE.to_vec(),
+            eip: 0,
+            rel_base: 0,
+            is_done: false,
+            code_written: false,
+            fallback: None,
+            pending_outputs: Vec::new(),
+        }
+    }
+
+    pub fn memory(&self) -> &[i64] {
+        match &self.fallback {
+            Some(intcode) => &intcode.memory,
+            None => &self.memory,
+        }
+    }
+
+    /// Give access to the memory, to patch dynamic cells. Patching
+    /// other code cells leads to undefined results.
+    pub fn memory_mut(&mut self) -> &mut Vec<i64> {
+        match &mut self.fallback {
+            Some(intcode) => &mut intcode.memory,
+            None => &mut self.memory,
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        match &self.fallback {
+            Some(intcode) => intcode.is_done(),
+            None => self.is_done,
+        }
+    }
+
+    /// Whether execution was handed over to the interpreter.
+    pub fn is_interpreted(&self) -> bool {
+        self.fallback.is_some()
+    }
+
+    pub fn run(&mut self, inputs: &[i64]) -> Vec<i64> {
+        match self.try_run(inputs) {
+            Ok(output) => output,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Run the program, keeping the outputs produced before an error
+    /// for the next run, like `Intcode::try_run`.
+    pub fn try_run(&mut self, inputs: &[i64]) -> Result<Vec<i64>> {
+        let mut io = Io {
+            inputs,
+            pos: 0,
+            output: std::mem::take(&mut self.pending_outputs),
+        };
+
+        if self.fallback.is_none() {
+            loop {
+                match self.dispatch(&mut io) {
+                    Ok(Flow::Next) => (),
+                    Ok(Flow::Stop) => return Ok(io.output),
+                    Ok(Flow::Fallback) => break,
+                    Err(e) => {
+                        self.pending_outputs = io.output;
+                        return Err(e);
+                    }
+                }
+            }
+            let mut intcode = Intcode::new(&self.memory);
+            intcode.set_eip(self.eip);
+            intcode.set_rel_base(self.rel_base);
+            self.memory = Vec::new();
+            self.fallback = Some(intcode);
+        }
+
+        let intcode = self.fallback.as_mut().unwrap();
+        match intcode.try_run(&inputs[io.pos..]) {
+            Ok(output) => {
+                io.output.extend(output);
+                Ok(io.output)
+            }
+            Err(e) => {
+                io.output.extend(intcode.take_pending_outputs());
+                self.pending_outputs = io.output;
+                Err(e)
+            }
+        }
+    }
+
+    /// Outputs produced by runs interrupted by an error, which are
+    /// otherwise returned first by the next run.
+    pub fn take_pending_outputs(&mut self) -> Vec<i64> {
+        std::mem::take(&mut self.pending_outputs)
+    }
+
+    #[inline]
+    fn grow(&mut self, addr: usize) -> Result<()> {
+        if self.memory.try_reserve(addr + 1 - self.memory.len()).is_err() {
+            return Err(Error::InvalidAddress(addr as i64));
+        }
+        self.memory.resize(addr + 1, 0);
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    #[inline]
+    fn rd(&mut self, a: i64) -> Result<i64> {
+        let a = addr(a)?;
+        if a >= self.memory.len() {
+            self.grow(a)?;
+        }
+        Ok(self.memory[a])
+    }
+
+    /// Write `v` at `a`, returning whether it modified the code.
+    #[allow(dead_code)]
+    #[inline]
+    fn wr(&mut self, a: i64, v: i64) -> Result<bool> {
+        let a = addr(a)?;
+        if a >= self.memory.len() {
+            self.grow(a)?;
+        }
+        self.memory[a] = v;
+        if is_code(a) && IMAGE.get(a).unwrap_or(&0) != &v {
+            self.code_written = true;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Does the code in `start..end` differ from the translation?
+    #[allow(dead_code)]
+    #[inline]
+    fn modified(&self, start: usize, end: usize) -> bool {
+        self.code_written
+            && (start..end).any(|a| {
+                is_code(a) && self.memory.get(a).unwrap_or(&0) != IMAGE.get(a).unwrap_or(&0)
+            })
+    }
+
+    #[allow(dead_code)]
+    #[inline]
+    fn rel(&self, v: i64) -> Result<i64> {
+        v.checked_add(self.rel_base).ok_or(Error::Overflow)
+    }
+"#;
+
+/// Translate the program in `memory` into a Rust module, see the
+/// module documentation. The cells in `dynamic` are read at runtime.
+pub fn translate(memory: &[i64], dynamic: &[usize]) -> String {
+    let image = Image {
+        memory,
+        dynamic: dynamic.iter().cloned().collect(),
+    };
+    let analysis = analyze(&image);
+    let mut gen = Gen {
+        image: &image,
+        analysis: &analysis,
+        out: String::from(PRELUDE),
+        end: 0,
+    };
+
+    // dispatch
+    writeln!(gen.out).unwrap();
+    writeln!(gen.out, "    #[inline]").unwrap();
+    writeln!(
+        gen.out,
+        "    fn dispatch(&mut self, io: &mut Io) -> Result<Flow> {{"
+    )
+    .unwrap();
+    writeln!(gen.out, "        match self.eip {{").unwrap();
+    for leader in &analysis.leaders {
+        let end = gen.block_end(*leader);
+        writeln!(
+            gen.out,
+            "            {} if self.modified({}, {}) => Ok(Flow::Fallback),",
+            leader, leader, end
+        )
+        .unwrap();
+        writeln!(gen.out, "            {} => self.b{}(io),", leader, leader).unwrap();
+    }
+    writeln!(gen.out, "            _ => Ok(Flow::Fallback),").unwrap();
+    writeln!(gen.out, "        }}").unwrap();
+    writeln!(gen.out, "    }}").unwrap();
+
+    for leader in &analysis.leaders {
+        gen.block(*leader);
+    }
+    writeln!(gen.out, "}}").unwrap();
+
+    // initial image, and code map
+    let code_len = analysis.code.iter().max().map_or(0, |max| max + 1);
+    writeln!(gen.out).unwrap();
+    writeln!(gen.out, "const IMAGE: [i64; {}] = [", memory.len()).unwrap();
+    for chunk in memory.chunks(16) {
+        let values: Vec<_> = chunk.iter().map(|v| v.to_string()).collect();
+        writeln!(gen.out, "    {},", values.join(", ")).unwrap();
+    }
+    writeln!(gen.out, "];").unwrap();
+    writeln!(gen.out).unwrap();
+    writeln!(gen.out, "static CODE: [bool; {}] = [", code_len).unwrap();
+    let code: Vec<_> = (0..code_len)
+        .map(|addr| analysis.code.contains(&addr))
+        .collect();
+    for chunk in code.chunks(16) {
+        let values: Vec<_> = chunk.iter().map(|v| v.to_string()).collect();
+        writeln!(gen.out, "    {},", values.join(", ")).unwrap();
+    }
+    writeln!(gen.out, "];").unwrap();
+
+    gen.out
+}
+
+// }}}
@@ -0,0 +1,527 @@
+//! GDB remote serial protocol stub.
+//!
+//! Exposes an `Intcode` instance to GDB (or any other client speaking
+//! the protocol) over TCP:
+//!
+//! ```text
+//! (gdb) target remote localhost:1234
+//! ```
+//!
+//! GDB works with byte addresses: memory cell `n` is exposed as the 8
+//! bytes at address `8 * n`, in little-endian. Two registers are
+//! available: `eip`, as a byte address, and `rel_base`, as a cell
+//! offset.
+//!
+//! Inputs are given to the program with `monitor input 1,2,3`, and
+//! the values output so far are displayed with `monitor outputs`.
+
+use crate::{Error, Intcode, Step};
+use std::collections::{HashSet, VecDeque};
+use std::io;
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+
+const CELL_SIZE: usize = 8;
+
+/// Maximum number of bytes returned for a single memory read.
+const MAX_READ: usize = 0x1000;
+
+/// Number of instructions executed between two checks of an interrupt
+/// request while continuing.
+const INTERRUPT_CHECK_PERIOD: usize = 4096;
+
+const TARGET_XML: &str = r#"<?xml version="1.0"?>
+<!DOCTYPE target SYSTEM "gdb-target.dtd">
+<target version="1.0">
+  <feature name="org.intcode.core">
+    <reg name="eip" bitsize="64" type="code_ptr" regnum="0"/>
+    <reg name="rel_base" bitsize="64" type="int64" regnum="1"/>
+  </feature>
+</target>
+"#;
+
+/// Reason for the program to stop, reported to the client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Stop {
+    Trap,
+    Interrupted,
+    WaitingForInput,
+    Halted,
+    Error(Error),
+}
+
+impl Stop {
+    fn reply(&self) -> String {
+        // signal numbers are the ones defined by GDB, not the host
+        match self {
+            Self::Trap => "S05".to_owned(),
+            Self::Interrupted => "S02".to_owned(),
+            Self::WaitingForInput => "S11".to_owned(),
+            Self::Halted => "W00".to_owned(),
+            Self::Error(Error::InvalidAddress(_)) | Self::Error(Error::MemoryLimit(_)) => {
+                "S0b".to_owned()
+            }
+            Self::Error(_) => "S04".to_owned(),
+        }
+    }
+}
+
+pub struct Server {
+    intcode: Intcode,
+    inputs: VecDeque<i64>,
+    outputs: Vec<i64>,
+    breakpoints: HashSet<usize>,
+    last_stop: Stop,
+}
+
+impl Server {
+    pub fn new(intcode: Intcode, inputs: &[i64]) -> Self {
+        Self {
+            intcode,
+            inputs: inputs.iter().cloned().collect(),
+            outputs: Vec::new(),
+            breakpoints: HashSet::new(),
+            last_stop: Stop::Trap,
+        }
+    }
+
+    /// Wait for a client on `listener`, and serve it until it detaches
+    /// or kills the program.
+    pub fn serve(&mut self, listener: &TcpListener) -> io::Result<()> {
+        let (stream, _) = listener.accept()?;
+        self.handle(stream)
+    }
+
+    /// Serve a client connected on `stream`, until it detaches or kills
+    /// the program.
+    pub fn handle(&mut self, stream: TcpStream) -> io::Result<()> {
+        // packets and acks are tiny, do not wait to coalesce them
+        stream.set_nodelay(true)?;
+        let mut conn = Connection::new(stream);
+
+        while let Some(packet) = conn.read_packet()? {
+            let packet = String::from_utf8_lossy(&packet).into_owned();
+            if packet.starts_with('k') {
+                return Ok(());
+            } else if packet.starts_with('D') {
+                conn.send_packet(b"OK")?;
+                return Ok(());
+            } else {
+                let reply = self.handle_packet(&packet, &mut conn)?;
+                conn.send_packet(reply.as_bytes())?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn intcode(&self) -> &Intcode {
+        &self.intcode
+    }
+
+    pub fn outputs(&self) -> &[i64] {
+        &self.outputs
+    }
+
+    fn handle_packet(&mut self, packet: &str, conn: &mut Connection) -> io::Result<String> {
+        let (cmd, args) = match (packet.get(..1), packet.get(1..)) {
+            (Some(cmd), Some(args)) => (cmd, args),
+            _ => return Ok(String::new()),
+        };
+
+        let reply = match cmd {
+            "?" => self.last_stop.reply(),
+            "g" => {
+                let mut regs = Vec::new();
+                for regno in 0..2 {
+                    regs.extend(&self.register_value(regno).unwrap().to_le_bytes());
+                }
+                to_hex(&regs)
+            }
+            "G" => self.write_registers(args).unwrap_or_else(error),
+            "p" => self.read_register(args).unwrap_or_else(error),
+            "P" => self.write_register(args).unwrap_or_else(error),
+            "m" => self.read_memory(args).unwrap_or_else(error),
+            "M" => self.write_memory(args).unwrap_or_else(error),
+            "Z" | "z" => self.set_breakpoint(args, cmd == "Z").unwrap_or_default(),
+            "s" => {
+                self.last_stop = self.step();
+                self.last_stop.reply()
+            }
+            "c" => {
+                self.last_stop = self.cont(conn)?;
+                self.last_stop.reply()
+            }
+            "H" | "T" => "OK".to_owned(),
+            "q" => self.query(args),
+            _ => String::new(),
+        };
+        Ok(reply)
+    }
+
+    fn query(&mut self, query: &str) -> String {
+        if query.starts_with("Supported") {
+            "PacketSize=1000;qXfer:features:read+".to_owned()
+        } else if let Some(args) = query.strip_prefix("Xfer:features:read:target.xml:") {
+            read_xfer(TARGET_XML.as_bytes(), args).unwrap_or_else(error)
+        } else if let Some(cmd) = query.strip_prefix("Rcmd,") {
+            self.monitor(cmd).unwrap_or_else(error)
+        } else {
+            match query {
+                "Attached" => "1".to_owned(),
+                "C" => "QC1".to_owned(),
+                "fThreadInfo" => "m1".to_owned(),
+                "sThreadInfo" => "l".to_owned(),
+                _ => String::new(),
+            }
+        }
+    }
+
+    fn monitor(&mut self, cmd: &str) -> Option<String> {
+        let cmd = String::from_utf8(from_hex(cmd)?).ok()?;
+        let mut words = cmd.trim().splitn(2, ' ');
+
+        match words.next()? {
+            "input" => {
+                let values = words
+                    .next()
+                    .unwrap_or("")
+                    .split(',')
+                    .map(|v| v.trim().parse().ok())
+                    .collect::<Option<Vec<i64>>>()?;
+                self.inputs.extend(values);
+                Some("OK".to_owned())
+            }
+            "outputs" => {
+                let outputs: Vec<_> = self.outputs.iter().map(|v| v.to_string()).collect();
+                Some(to_hex(format!("{}\n", outputs.join(",")).as_bytes()))
+            }
+            _ => None,
+        }
+    }
+
+    fn register_value(&self, regno: usize) -> Option<u64> {
+        match regno {
+            0 => Some((self.intcode.eip() * CELL_SIZE) as u64),
+            1 => Some(self.intcode.rel_base() as u64),
+            _ => None,
+        }
+    }
+
+    fn set_register_value(&mut self, regno: usize, value: u64) -> Option<()> {
+        match regno {
+            0 => {
+                let value = value as usize;
+                if !value.is_multiple_of(CELL_SIZE) {
+                    return None;
+                }
+                self.intcode.set_eip(value / CELL_SIZE);
+            }
+            1 => self.intcode.set_rel_base(value as i64),
+            _ => return None,
+        }
+        Some(())
+    }
+
+    fn read_register(&self, args: &str) -> Option<String> {
+        let regno = usize::from_str_radix(args, 16).ok()?;
+        Some(to_hex(&self.register_value(regno)?.to_le_bytes()))
+    }
+
+    fn write_register(&mut self, args: &str) -> Option<String> {
+        let mut parts = args.splitn(2, '=');
+        let regno = usize::from_str_radix(parts.next()?, 16).ok()?;
+        let value = u64_from_le_hex(parts.next()?)?;
+
+        self.set_register_value(regno, value)?;
+        Some("OK".to_owned())
+    }
+
+    fn write_registers(&mut self, args: &str) -> Option<String> {
+        if args.len() != 32 || !args.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        let eip = u64_from_le_hex(&args[0..16])?;
+        let rel_base = u64_from_le_hex(&args[16..32])?;
+
+        self.set_register_value(0, eip)?;
+        self.set_register_value(1, rel_base)?;
+        Some("OK".to_owned())
+    }
+
+    fn read_memory(&self, args: &str) -> Option<String> {
+        let (addr, len) = parse_addr_len(args)?;
+        let len = std::cmp::min(len, MAX_READ);
+
+        let bytes: Vec<u8> = (addr..addr.checked_add(len)?)
+            .map(|a| {
+                let cell = self.intcode.memory.get(a / CELL_SIZE).unwrap_or(&0);
+                cell.to_le_bytes()[a % CELL_SIZE]
+            })
+            .collect();
+        Some(to_hex(&bytes))
+    }
+
+    fn write_memory(&mut self, args: &str) -> Option<String> {
+        let mut parts = args.splitn(2, ':');
+        let (addr, len) = parse_addr_len(parts.next()?)?;
+        let bytes = from_hex(parts.next()?)?;
+        if bytes.len() != len {
+            return None;
+        }
+
+        let end = addr.checked_add(len)?;
+        let nb_cells = end.div_ceil(CELL_SIZE);
+        // the debugger cannot grow the memory past what the program may
+        if let Some(max) = self.intcode.limits().max_memory {
+            if nb_cells > max {
+                return None;
+            }
+        }
+        let memory = &mut self.intcode.memory;
+        if nb_cells > memory.len() {
+            memory.try_reserve(nb_cells - memory.len()).ok()?;
+            memory.resize(nb_cells, 0);
+        }
+        for (a, byte) in (addr..end).zip(bytes) {
+            let cell = &mut memory[a / CELL_SIZE];
+            let mut cell_bytes = cell.to_le_bytes();
+            cell_bytes[a % CELL_SIZE] = byte;
+            *cell = i64::from_le_bytes(cell_bytes);
+        }
+        Some("OK".to_owned())
+    }
+
+    /// Insert or remove a breakpoint. Returns `None` for unsupported
+    /// kinds of breakpoints.
+    fn set_breakpoint(&mut self, args: &str, insert: bool) -> Option<String> {
+        let mut parts = args.split(',');
+        // software and hardware breakpoints are the same thing here
+        match parts.next()? {
+            "0" | "1" => (),
+            _ => return None,
+        }
+        let addr = usize::from_str_radix(parts.next()?, 16).ok()?;
+        if !addr.is_multiple_of(CELL_SIZE) {
+            return Some(error());
+        }
+
+        if insert {
+            self.breakpoints.insert(addr / CELL_SIZE);
+        } else {
+            self.breakpoints.remove(&(addr / CELL_SIZE));
+        }
+        Some("OK".to_owned())
+    }
+
+    /// Execute a single instruction, returning `None` if it went
+    /// through without stopping the program.
+    fn exec(&mut self) -> Option<Stop> {
+        let input = self.inputs.front().cloned();
+
+        match self.intcode.try_step(input) {
+            Ok(Step::Executed {
+                consumed_input,
+                output,
+            }) => {
+                if consumed_input {
+                    self.inputs.pop_front();
+                }
+                self.outputs.extend(output);
+                None
+            }
+            Ok(Step::WaitingForInput) => Some(Stop::WaitingForInput),
+            Ok(Step::Halted) => Some(Stop::Halted),
+            Err(e) => Some(Stop::Error(e)),
+        }
+    }
+
+    fn step(&mut self) -> Stop {
+        self.exec().unwrap_or(Stop::Trap)
+    }
+
+    fn cont(&mut self, conn: &mut Connection) -> io::Result<Stop> {
+        let mut count = 0;
+
+        loop {
+            if let Some(stop) = self.exec() {
+                return Ok(stop);
+            }
+            if self.breakpoints.contains(&self.intcode.eip()) {
+                return Ok(Stop::Trap);
+            }
+            count += 1;
+            if count % INTERRUPT_CHECK_PERIOD == 0 && conn.poll_interrupt()? {
+                return Ok(Stop::Interrupted);
+            }
+        }
+    }
+}
+
+// {{{ Connection
+
+struct Connection {
+    stream: TcpStream,
+    buf: VecDeque<u8>,
+}
+
+impl Connection {
+    fn new(stream: TcpStream) -> Self {
+        Self {
+            stream,
+            buf: VecDeque::new(),
+        }
+    }
+
+    fn fill(&mut self) -> io::Result<usize> {
+        let mut tmp = [0; 1024];
+        let n = self.stream.read(&mut tmp)?;
+        self.buf.extend(&tmp[..n]);
+        Ok(n)
+    }
+
+    fn read_byte(&mut self) -> io::Result<Option<u8>> {
+        if self.buf.is_empty() && self.fill()? == 0 {
+            return Ok(None);
+        }
+        Ok(self.buf.pop_front())
+    }
+
+    /// Check without blocking whether the client sent an interrupt
+    /// request.
+    fn poll_interrupt(&mut self) -> io::Result<bool> {
+        self.stream.set_nonblocking(true)?;
+        let res = self.fill();
+        self.stream.set_nonblocking(false)?;
+        match res {
+            Ok(_) => (),
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => (),
+            Err(e) => return Err(e),
+        }
+
+        match self.buf.iter().position(|b| *b == 0x03) {
+            Some(pos) => {
+                self.buf.remove(pos);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Read the next packet, acknowledging it. Returns `None` when the
+    /// client disconnects.
+    fn read_packet(&mut self) -> io::Result<Option<Vec<u8>>> {
+        loop {
+            // skip acks, and interrupts received while stopped
+            match self.read_byte()? {
+                None => return Ok(None),
+                Some(b'$') => (),
+                Some(_) => continue,
+            }
+
+            let mut data = Vec::new();
+            loop {
+                match self.read_byte()? {
+                    None => return Ok(None),
+                    Some(b'#') => break,
+                    Some(b) => data.push(b),
+                }
+            }
+            let mut checksum = [0; 2];
+            for c in checksum.iter_mut() {
+                match self.read_byte()? {
+                    None => return Ok(None),
+                    Some(b) => *c = b,
+                }
+            }
+
+            let expected = std::str::from_utf8(&checksum)
+                .ok()
+                .and_then(|s| u8::from_str_radix(s, 16).ok());
+            if expected == Some(checksum_of(&data)) {
+                self.stream.write_all(b"+")?;
+                return Ok(Some(data));
+            }
+            self.stream.write_all(b"-")?;
+        }
+    }
+
+    /// Send a packet, until the client acknowledges it.
+    fn send_packet(&mut self, data: &[u8]) -> io::Result<()> {
+        let mut packet = Vec::with_capacity(data.len() + 4);
+        packet.push(b'$');
+        packet.extend(data);
+        packet.extend(format!("#{:02x}", checksum_of(data)).as_bytes());
+
+        loop {
+            self.stream.write_all(&packet)?;
+            match self.read_byte()? {
+                Some(b'-') => continue,
+                Some(b'+') | None => return Ok(()),
+                // not an ack, keep it for the next read
+                Some(b) => {
+                    self.buf.push_front(b);
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+// }}}
+// {{{ Encoding helpers
+
+fn error() -> String {
+    "E01".to_owned()
+}
+
+fn checksum_of(data: &[u8]) -> u8 {
+    data.iter().fold(0u8, |acc, b| acc.wrapping_add(*b))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..(i + 2))?, 16).ok())
+        .collect()
+}
+
+fn u64_from_le_hex(s: &str) -> Option<u64> {
+    let bytes = from_hex(s)?;
+    if bytes.len() != 8 {
+        return None;
+    }
+    let mut le = [0; 8];
+    le.copy_from_slice(&bytes);
+    Some(u64::from_le_bytes(le))
+}
+
+fn parse_addr_len(s: &str) -> Option<(usize, usize)> {
+    let mut parts = s.splitn(2, ',');
+    let addr = usize::from_str_radix(parts.next()?, 16).ok()?;
+    let len = usize::from_str_radix(parts.next()?, 16).ok()?;
+    Some((addr, len))
+}
+
+fn read_xfer(data: &[u8], args: &str) -> Option<String> {
+    let (offset, len) = parse_addr_len(args)?;
+    if offset > data.len() {
+        return None;
+    }
+    let end = std::cmp::min(data.len(), offset.saturating_add(len));
+    let prefix = if end == data.len() { 'l' } else { 'm' };
+
+    Some(format!(
+        "{}{}",
+        prefix,
+        String::from_utf8_lossy(&data[offset..end])
+    ))
+}
+
+// }}}
@@ -0,0 +1,73 @@
+use crate::{Error, Result};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+type HostFn = dyn Fn(&[i64], &mut [i64]) -> Result<()> + Send + Sync;
+
+#[derive(Clone)]
+struct HostCall {
+    nb_args: usize,
+    nb_results: usize,
+    func: Arc<HostFn>,
+}
+
+/// Set of host functions callable from an Intcode program.
+///
+/// Once enabled with `Intcode::enable_host_calls`, the program can use
+/// the host-call opcode with three parameters:
+///
+/// - the number of the function to call,
+/// - the address of its arguments (position or relative mode),
+/// - the address where its results are written (position or relative
+///   mode).
+///
+/// Only the functions registered here are reachable from the program.
+#[derive(Clone, Default)]
+pub struct HostCalls {
+    calls: HashMap<i64, HostCall>,
+}
+
+impl HostCalls {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register function `number`, reading `nb_args` arguments and
+    /// writing `nb_results` results.
+    ///
+    /// `func` receives the arguments, and a slice of `nb_results`
+    /// zeroes to fill. Registering the same number twice replaces the
+    /// function.
+    pub fn register<F>(&mut self, number: i64, nb_args: usize, nb_results: usize, func: F)
+    where
+        F: Fn(&[i64], &mut [i64]) -> Result<()> + Send + Sync + 'static,
+    {
+        self.calls.insert(
+            number,
+            HostCall {
+                nb_args,
+                nb_results,
+                func: Arc::new(func),
+            },
+        );
+    }
+
+    /// Call function `number`: `read` is used to fetch its arguments,
+    /// and the results are returned.
+    pub(crate) fn call<R>(&self, number: i64, mut read: R) -> Result<Vec<i64>>
+    where
+        R: FnMut(usize) -> Result<i64>,
+    {
+        let call = self
+            .calls
+            .get(&number)
+            .ok_or(Error::UnknownHostCall(number))?;
+
+        let args = (0..call.nb_args)
+            .map(&mut read)
+            .collect::<Result<Vec<_>>>()?;
+        let mut results = vec![0; call.nb_results];
+        (call.func)(&args, &mut results)?;
+        Ok(results)
+    }
+}
@@ -0,0 +1,460 @@
+//! Code generation.
+//!
+//! The relative base points to the frame of the current function,
+//! which holds the return address, then the parameters, the local
+//! variables and the temporaries used to evaluate expressions. To call
+//! a function, the caller writes the arguments and the return address
+//! right after its own frame, and moves the relative base there. The
+//! returned value is passed through a fixed cell placed after the code.
+
+use super::parser::{BinOp, Expr, Function, Stmt, UnOp};
+use super::{CompileError, Pos};
+use std::collections::HashMap;
+
+type Result<T> = std::result::Result<T, CompileError>;
+
+#[derive(Debug, Clone, Copy)]
+enum Value {
+    Const(i64),
+    /// Address of a label, resolved once all the code is generated.
+    Label(usize),
+    /// `factor * frame_size + offset`, resolved at the end of the
+    /// function, when the size of its frame is known.
+    Frame(i64, i64),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Arg {
+    Pos(Value),
+    Imm(Value),
+    Rel(Value),
+}
+
+impl Arg {
+    fn mode(&self) -> i64 {
+        match self {
+            Self::Pos(_) => 0,
+            Self::Imm(_) => 1,
+            Self::Rel(_) => 2,
+        }
+    }
+
+    fn value(&self) -> Value {
+        match self {
+            Self::Pos(v) | Self::Imm(v) | Self::Rel(v) => *v,
+        }
+    }
+}
+
+fn imm(v: i64) -> Arg {
+    Arg::Imm(Value::Const(v))
+}
+
+fn slot(s: i64) -> Arg {
+    Arg::Rel(Value::Const(s))
+}
+
+const BUILTINS: [&str; 2] = ["input", "output"];
+
+struct Gen<'a> {
+    code: Vec<i64>,
+    labels: Vec<Option<usize>>,
+    label_refs: Vec<(usize, usize)>,
+    /// Function name to label and arity.
+    functions: HashMap<&'a str, (usize, usize)>,
+    /// Label of the cell holding returned values.
+    ret_value: usize,
+
+    // state of the current function
+    frame_refs: Vec<(usize, i64, i64)>,
+    scopes: Vec<HashMap<&'a str, i64>>,
+    next_local: i64,
+    temps_base: i64,
+    depth: i64,
+    max_depth: i64,
+}
+
+fn count_lets(stmts: &[Stmt]) -> i64 {
+    stmts
+        .iter()
+        .map(|stmt| match stmt {
+            Stmt::Let(..) => 1,
+            Stmt::If(_, then, otherwise) => count_lets(then) + count_lets(otherwise),
+            Stmt::While(_, body) => count_lets(body),
+            _ => 0,
+        })
+        .sum()
+}
+
+impl<'a> Gen<'a> {
+    fn new_label(&mut self) -> usize {
+        self.labels.push(None);
+        self.labels.len() - 1
+    }
+
+    fn place(&mut self, label: usize) {
+        self.labels[label] = Some(self.code.len());
+    }
+
+    fn emit(&mut self, opcode: i64, args: &[Arg]) {
+        let mut instruction = opcode;
+        let mut factor = 100;
+        for arg in args {
+            instruction += arg.mode() * factor;
+            factor *= 10;
+        }
+        self.code.push(instruction);
+
+        for arg in args {
+            let pos = self.code.len();
+            match arg.value() {
+                Value::Const(v) => self.code.push(v),
+                Value::Label(label) => {
+                    self.code.push(0);
+                    self.label_refs.push((pos, label));
+                }
+                Value::Frame(factor, offset) => {
+                    self.code.push(0);
+                    self.frame_refs.push((pos, factor, offset));
+                }
+            }
+        }
+    }
+
+    fn jump_if_false(&mut self, cond: &'a Expr, label: usize) -> Result<()> {
+        let cond = self.operand(cond)?;
+        self.emit(6, &[cond, Arg::Imm(Value::Label(label))]);
+        Ok(())
+    }
+
+    fn jump(&mut self, label: usize) {
+        self.emit(5, &[imm(1), Arg::Imm(Value::Label(label))]);
+    }
+
+    fn temp(&mut self) -> Arg {
+        let t = self.temps_base + self.depth;
+        self.depth += 1;
+        self.max_depth = std::cmp::max(self.max_depth, self.depth);
+        slot(t)
+    }
+
+    fn lookup(&self, name: &str, pos: Pos) -> Result<i64> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name).cloned())
+            .ok_or_else(|| CompileError::new(pos, format!("unknown variable `{}`", name)))
+    }
+
+    fn function(&mut self, f: &'a Function) -> Result<()> {
+        let (label, _) = self.functions[f.name.as_str()];
+        self.place(label);
+
+        let mut params = HashMap::new();
+        for (idx, param) in f.params.iter().enumerate() {
+            if params.insert(param.as_str(), 1 + idx as i64).is_some() {
+                return Err(CompileError::new(
+                    f.pos,
+                    format!("parameter `{}` is already defined", param),
+                ));
+            }
+        }
+        self.scopes = vec![params];
+        self.next_local = 1 + f.params.len() as i64;
+        self.temps_base = self.next_local + count_lets(&f.body);
+        self.max_depth = 0;
+        self.frame_refs.clear();
+
+        for stmt in &f.body {
+            self.stmt(stmt)?;
+        }
+        self.ret(None)?;
+
+        let frame_size = self.temps_base + self.max_depth;
+        for (pos, factor, offset) in self.frame_refs.drain(..) {
+            self.code[pos] = factor * frame_size + offset;
+        }
+        Ok(())
+    }
+
+    fn block(&mut self, stmts: &'a [Stmt]) -> Result<()> {
+        self.scopes.push(HashMap::new());
+        for stmt in stmts {
+            self.stmt(stmt)?;
+        }
+        self.scopes.pop();
+        Ok(())
+    }
+
+    fn ret(&mut self, value: Option<&'a Expr>) -> Result<()> {
+        let value = match value {
+            Some(value) => self.operand(value)?,
+            None => imm(0),
+        };
+        let ret_value = Arg::Pos(Value::Label(self.ret_value));
+        self.emit(1, &[value, imm(0), ret_value]);
+        // jump to the return address
+        self.emit(6, &[imm(0), slot(0)]);
+        Ok(())
+    }
+
+    fn stmt(&mut self, stmt: &'a Stmt) -> Result<()> {
+        // temporaries do not outlive statements
+        self.depth = 0;
+
+        match stmt {
+            Stmt::Let(name, value) => {
+                let local = self.next_local;
+                self.next_local += 1;
+                self.expr_into(value, slot(local))?;
+                self.scopes.last_mut().unwrap().insert(name, local);
+            }
+            Stmt::Assign(name, value, pos) => {
+                let local = self.lookup(name, *pos)?;
+                self.expr_into(value, slot(local))?;
+            }
+            Stmt::If(cond, then, otherwise) => {
+                let else_label = self.new_label();
+                let end_label = self.new_label();
+                self.jump_if_false(cond, else_label)?;
+                self.block(then)?;
+                if !otherwise.is_empty() {
+                    self.jump(end_label);
+                }
+                self.place(else_label);
+                self.block(otherwise)?;
+                self.place(end_label);
+            }
+            Stmt::While(cond, body) => {
+                let start_label = self.new_label();
+                let end_label = self.new_label();
+                self.place(start_label);
+                self.jump_if_false(cond, end_label)?;
+                self.block(body)?;
+                self.jump(start_label);
+                self.place(end_label);
+            }
+            Stmt::Return(value) => self.ret(value.as_ref())?,
+            Stmt::Expr(expr) => {
+                self.operand(expr)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Argument giving the value of `expr`, computed in a temporary if
+    /// needed.
+    fn operand(&mut self, expr: &'a Expr) -> Result<Arg> {
+        match expr {
+            Expr::Int(v) => Ok(imm(*v)),
+            Expr::Var(name, pos) => Ok(slot(self.lookup(name, *pos)?)),
+            Expr::Unary(UnOp::Neg, e) => match **e {
+                Expr::Int(v) if v != i64::MIN => Ok(imm(-v)),
+                _ => self.temp_operand(expr),
+            },
+            _ => self.temp_operand(expr),
+        }
+    }
+
+    fn temp_operand(&mut self, expr: &'a Expr) -> Result<Arg> {
+        let t = self.temp();
+        self.expr_into(expr, t)?;
+        Ok(t)
+    }
+
+    fn expr_into(&mut self, expr: &'a Expr, dest: Arg) -> Result<()> {
+        match expr {
+            Expr::Int(_) | Expr::Var(..) => {
+                let v = self.operand(expr)?;
+                self.emit(1, &[v, imm(0), dest]);
+            }
+            Expr::Unary(op, e) => {
+                let v = self.operand(e)?;
+                match op {
+                    UnOp::Neg => self.emit(2, &[v, imm(-1), dest]),
+                    UnOp::Not => self.emit(8, &[v, imm(0), dest]),
+                }
+            }
+            Expr::Binary(op @ BinOp::And, a, b) | Expr::Binary(op @ BinOp::Or, a, b) => {
+                // the result is `short` if `a` decides it
+                let short = if *op == BinOp::And { 0 } else { 1 };
+                let short_label = self.new_label();
+                let end_label = self.new_label();
+                for e in [&**a, &**b].iter() {
+                    let v = self.operand(e)?;
+                    let jump = if *op == BinOp::And { 6 } else { 5 };
+                    self.emit(jump, &[v, Arg::Imm(Value::Label(short_label))]);
+                }
+                self.emit(1, &[imm(1 - short), imm(0), dest]);
+                self.jump(end_label);
+                self.place(short_label);
+                self.emit(1, &[imm(short), imm(0), dest]);
+                self.place(end_label);
+            }
+            Expr::Binary(op, a, b) => {
+                let x = self.operand(a)?;
+                let y = self.operand(b)?;
+                match op {
+                    BinOp::Add => self.emit(1, &[x, y, dest]),
+                    BinOp::Mul => self.emit(2, &[x, y, dest]),
+                    BinOp::Sub => match y {
+                        Arg::Imm(Value::Const(v)) if v != i64::MIN => {
+                            self.emit(1, &[x, imm(-v), dest])
+                        }
+                        _ => {
+                            let t = self.temp();
+                            self.emit(2, &[y, imm(-1), t]);
+                            self.emit(1, &[x, t, dest]);
+                        }
+                    },
+                    BinOp::Lt => self.emit(7, &[x, y, dest]),
+                    BinOp::Gt => self.emit(7, &[y, x, dest]),
+                    BinOp::Eq => self.emit(8, &[x, y, dest]),
+                    // negations of the above
+                    BinOp::Ge | BinOp::Le | BinOp::Ne => {
+                        let t = self.temp();
+                        match op {
+                            BinOp::Ge => self.emit(7, &[x, y, t]),
+                            BinOp::Le => self.emit(7, &[y, x, t]),
+                            _ => self.emit(8, &[x, y, t]),
+                        }
+                        self.emit(8, &[t, imm(0), dest]);
+                    }
+                    BinOp::And | BinOp::Or => unreachable!(),
+                }
+            }
+            Expr::Call(name, args, pos) => self.call(name, args, *pos, dest)?,
+        }
+        Ok(())
+    }
+
+    fn call(&mut self, name: &'a str, args: &'a [Expr], pos: Pos, dest: Arg) -> Result<()> {
+        let arity = match name {
+            "input" => 0,
+            "output" => 1,
+            _ => match self.functions.get(name) {
+                Some((_, arity)) => *arity,
+                None => {
+                    return Err(CompileError::new(
+                        pos,
+                        format!("unknown function `{}`", name),
+                    ))
+                }
+            },
+        };
+        if args.len() != arity {
+            return Err(CompileError::new(
+                pos,
+                format!(
+                    "`{}` takes {} argument(s), {} given",
+                    name,
+                    arity,
+                    args.len()
+                ),
+            ));
+        }
+
+        match name {
+            "input" => self.emit(3, &[dest]),
+            "output" => {
+                let v = self.operand(&args[0])?;
+                self.emit(4, &[v]);
+                self.emit(1, &[imm(0), imm(0), dest]);
+            }
+            _ => {
+                let mut values = Vec::new();
+                for arg in args {
+                    values.push(self.operand(arg)?);
+                }
+                // arguments and return address in the next frame
+                for (idx, v) in values.into_iter().enumerate() {
+                    let param = Arg::Rel(Value::Frame(1, 1 + idx as i64));
+                    self.emit(1, &[v, imm(0), param]);
+                }
+                let ret_label = self.new_label();
+                let (label, _) = self.functions[name];
+                self.emit(
+                    1,
+                    &[
+                        Arg::Imm(Value::Label(ret_label)),
+                        imm(0),
+                        Arg::Rel(Value::Frame(1, 0)),
+                    ],
+                );
+                self.emit(9, &[Arg::Imm(Value::Frame(1, 0))]);
+                self.jump(label);
+
+                self.place(ret_label);
+                self.emit(9, &[Arg::Imm(Value::Frame(-1, 0))]);
+                let ret_value = Arg::Pos(Value::Label(self.ret_value));
+                self.emit(1, &[ret_value, imm(0), dest]);
+            }
+        }
+        Ok(())
+    }
+}
+
+pub(super) fn generate(functions: &[Function]) -> Result<Vec<i64>> {
+    let mut gen = Gen {
+        code: Vec::new(),
+        labels: Vec::new(),
+        label_refs: Vec::new(),
+        functions: HashMap::new(),
+        ret_value: 0,
+        frame_refs: Vec::new(),
+        scopes: Vec::new(),
+        next_local: 0,
+        temps_base: 0,
+        depth: 0,
+        max_depth: 0,
+    };
+
+    for f in functions {
+        if BUILTINS.contains(&f.name.as_str()) || gen.functions.contains_key(f.name.as_str()) {
+            return Err(CompileError::new(
+                f.pos,
+                format!("function `{}` is already defined", f.name),
+            ));
+        }
+        let label = gen.new_label();
+        gen.functions.insert(&f.name, (label, f.params.len()));
+    }
+    let main = match gen.functions.get("main") {
+        Some((label, 0)) => *label,
+        Some(_) => {
+            let f = functions.iter().find(|f| f.name == "main").unwrap();
+            return Err(CompileError::new(f.pos, "`main` takes no arguments"));
+        }
+        None => {
+            return Err(CompileError::new(
+                Pos { line: 1, column: 1 },
+                "no `main` function",
+            ))
+        }
+    };
+    gen.ret_value = gen.new_label();
+    let stack = gen.new_label();
+
+    // call main from an empty frame at the start of the stack, with a
+    // return address pointing to a halt
+    let halt = gen.new_label();
+    gen.emit(9, &[Arg::Imm(Value::Label(stack))]);
+    gen.emit(1, &[Arg::Imm(Value::Label(halt)), imm(0), slot(0)]);
+    gen.jump(main);
+    gen.place(halt);
+    gen.emit(99, &[]);
+
+    for f in functions {
+        gen.function(f)?;
+    }
+
+    let ret_value = gen.ret_value;
+    gen.place(ret_value);
+    gen.code.push(0);
+    gen.place(stack);
+
+    for (pos, label) in gen.label_refs.drain(..) {
+        gen.code[pos] = gen.labels[label].unwrap() as i64;
+    }
+    Ok(gen.code)
+}
@@ -0,0 +1,117 @@
+use super::{CompileError, Pos};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Token {
+    Int(i64),
+    Ident(String),
+    Fn,
+    Let,
+    If,
+    Else,
+    While,
+    Return,
+    /// Operators and punctuation.
+    Sym(&'static str),
+    Eof,
+}
+
+impl Token {
+    pub(super) fn describe(&self) -> String {
+        match self {
+            Self::Int(v) => format!("`{}`", v),
+            Self::Ident(name) => format!("`{}`", name),
+            Self::Fn => "`fn`".to_owned(),
+            Self::Let => "`let`".to_owned(),
+            Self::If => "`if`".to_owned(),
+            Self::Else => "`else`".to_owned(),
+            Self::While => "`while`".to_owned(),
+            Self::Return => "`return`".to_owned(),
+            Self::Sym(s) => format!("`{}`", s),
+            Self::Eof => "end of file".to_owned(),
+        }
+    }
+}
+
+/// Longest symbols first.
+const SYMBOLS: [&str; 19] = [
+    "<=", ">=", "==", "!=", "&&", "||", "(", ")", "{", "}", ",", ";", "=", "+", "-", "*", "!", "<",
+    ">",
+];
+
+pub(super) fn tokenize(source: &str) -> Result<Vec<(Token, Pos)>, CompileError> {
+    let mut tokens = Vec::new();
+
+    for (idx, line) in source.lines().enumerate() {
+        let chars: Vec<char> = line.chars().collect();
+        let mut col = 0;
+
+        while col < chars.len() {
+            let c = chars[col];
+            let pos = Pos {
+                line: idx + 1,
+                column: col + 1,
+            };
+            let start = col;
+
+            if c.is_whitespace() {
+                col += 1;
+                continue;
+            }
+            if c == '/' && chars.get(col + 1) == Some(&'/') {
+                break;
+            }
+
+            if c.is_ascii_digit() {
+                while col < chars.len() && chars[col].is_ascii_digit() {
+                    col += 1;
+                }
+                let s: String = chars[start..col].iter().collect();
+                let v = s
+                    .parse()
+                    .map_err(|_| CompileError::new(pos, format!("integer `{}` too large", s)))?;
+                tokens.push((Token::Int(v), pos));
+            } else if c.is_ascii_alphabetic() || c == '_' {
+                while col < chars.len() && (chars[col].is_ascii_alphanumeric() || chars[col] == '_')
+                {
+                    col += 1;
+                }
+                let s: String = chars[start..col].iter().collect();
+                let token = match s.as_str() {
+                    "fn" => Token::Fn,
+                    "let" => Token::Let,
+                    "if" => Token::If,
+                    "else" => Token::Else,
+                    "while" => Token::While,
+                    "return" => Token::Return,
+                    _ => Token::Ident(s),
+                };
+                tokens.push((token, pos));
+            } else {
+                let sym = SYMBOLS.iter().find(|sym| {
+                    sym.chars()
+                        .enumerate()
+                        .all(|(i, c)| chars.get(col + i) == Some(&c))
+                });
+                match sym {
+                    Some(sym) => {
+                        col += sym.len();
+                        tokens.push((Token::Sym(sym), pos));
+                    }
+                    None => {
+                        return Err(CompileError::new(
+                            pos,
+                            format!("unexpected character `{}`", c),
+                        ))
+                    }
+                }
+            }
+        }
+    }
+
+    let end = Pos {
+        line: source.lines().count() + 1,
+        column: 1,
+    };
+    tokens.push((Token::Eof, end));
+    Ok(tokens)
+}
@@ -0,0 +1,74 @@
+//! A small language compiled to Intcode.
+//!
+//! A program is a list of functions, executed from `main`:
+//!
+//! ```text
+//! // outputs the factorial of each input, until 0 is read
+//! fn fact(n) {
+//!     if n < 2 {
+//!         return 1;
+//!     }
+//!     return n * fact(n - 1);
+//! }
+//!
+//! fn main() {
+//!     let n = input();
+//!     while n != 0 {
+//!         output(fact(n));
+//!         n = input();
+//!     }
+//! }
+//! ```
+//!
+//! All values are integers. The statements are `let`, assignments, `if`
+//! with an optional `else`, `while`, `return` and expressions. The
+//! operators are `+ - *`, the comparisons `< <= > >= == !=`, which give
+//! 0 or 1, `!`, and the short-circuiting `&&` and `||`. `input()`
+//! returns the next input, and `output(x)` outputs `x`.
+//!
+//! Functions return 0 if they end without a `return`. Their frames live
+//! on a stack placed after the code, addressed through the relative
+//! base.
+
+mod codegen;
+mod lexer;
+mod parser;
+
+use std::fmt;
+
+/// Position in the source, starting at 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pos {
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompileError {
+    pub pos: Pos,
+    pub message: String,
+}
+
+impl CompileError {
+    fn new<S: Into<String>>(pos: Pos, message: S) -> Self {
+        Self {
+            pos,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for CompileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.pos.line, self.pos.column, self.message)
+    }
+}
+
+impl std::error::Error for CompileError {}
+
+/// Compile `source` to an Intcode program.
+pub fn compile(source: &str) -> Result<Vec<i64>, CompileError> {
+    let tokens = lexer::tokenize(source)?;
+    let functions = parser::parse(&tokens)?;
+    codegen::generate(&functions)
+}
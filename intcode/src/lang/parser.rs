@@ -0,0 +1,298 @@
+use super::lexer::Token;
+use super::{CompileError, Pos};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum UnOp {
+    Neg,
+    Not,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+    And,
+    Or,
+}
+
+#[derive(Debug)]
+pub(super) enum Expr {
+    Int(i64),
+    Var(String, Pos),
+    Call(String, Vec<Expr>, Pos),
+    Unary(UnOp, Box<Expr>),
+    Binary(BinOp, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Debug)]
+pub(super) enum Stmt {
+    Let(String, Expr),
+    Assign(String, Expr, Pos),
+    If(Expr, Vec<Stmt>, Vec<Stmt>),
+    While(Expr, Vec<Stmt>),
+    Return(Option<Expr>),
+    Expr(Expr),
+}
+
+#[derive(Debug)]
+pub(super) struct Function {
+    pub name: String,
+    pub params: Vec<String>,
+    pub body: Vec<Stmt>,
+    pub pos: Pos,
+}
+
+/// Binary operators by increasing precedence.
+const LEVELS: [&[(&str, BinOp)]; 5] = [
+    &[("||", BinOp::Or)],
+    &[("&&", BinOp::And)],
+    &[
+        ("==", BinOp::Eq),
+        ("!=", BinOp::Ne),
+        ("<", BinOp::Lt),
+        ("<=", BinOp::Le),
+        (">", BinOp::Gt),
+        (">=", BinOp::Ge),
+    ],
+    &[("+", BinOp::Add), ("-", BinOp::Sub)],
+    &[("*", BinOp::Mul)],
+];
+
+struct Parser<'a> {
+    tokens: &'a [(Token, Pos)],
+    idx: usize,
+}
+
+type Result<T> = std::result::Result<T, CompileError>;
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.idx].0
+    }
+
+    fn pos(&self) -> Pos {
+        self.tokens[self.idx].1
+    }
+
+    fn unexpected<T>(&self, expected: &str) -> Result<T> {
+        Err(CompileError::new(
+            self.pos(),
+            format!("expected {}, found {}", expected, self.peek().describe()),
+        ))
+    }
+
+    fn eat(&mut self, sym: &str) -> bool {
+        match self.peek() {
+            Token::Sym(s) if *s == sym => {
+                self.idx += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn expect(&mut self, sym: &str) -> Result<()> {
+        if self.eat(sym) {
+            Ok(())
+        } else {
+            self.unexpected(&format!("`{}`", sym))
+        }
+    }
+
+    fn ident(&mut self) -> Result<String> {
+        match self.peek().clone() {
+            Token::Ident(name) => {
+                self.idx += 1;
+                Ok(name)
+            }
+            _ => self.unexpected("an identifier"),
+        }
+    }
+
+    fn function(&mut self) -> Result<Function> {
+        let pos = self.pos();
+        if *self.peek() != Token::Fn {
+            return self.unexpected("`fn`");
+        }
+        self.idx += 1;
+        let name = self.ident()?;
+
+        self.expect("(")?;
+        let mut params = Vec::new();
+        if !self.eat(")") {
+            loop {
+                params.push(self.ident()?);
+                if self.eat(")") {
+                    break;
+                }
+                self.expect(",")?;
+            }
+        }
+        let body = self.block()?;
+
+        Ok(Function {
+            name,
+            params,
+            body,
+            pos,
+        })
+    }
+
+    fn block(&mut self) -> Result<Vec<Stmt>> {
+        self.expect("{")?;
+        let mut stmts = Vec::new();
+        while !self.eat("}") {
+            stmts.push(self.stmt()?);
+        }
+        Ok(stmts)
+    }
+
+    fn stmt(&mut self) -> Result<Stmt> {
+        match self.peek() {
+            Token::Let => {
+                self.idx += 1;
+                let name = self.ident()?;
+                self.expect("=")?;
+                let value = self.expr()?;
+                self.expect(";")?;
+                Ok(Stmt::Let(name, value))
+            }
+            Token::If => {
+                self.idx += 1;
+                self.if_stmt()
+            }
+            Token::While => {
+                self.idx += 1;
+                let cond = self.expr()?;
+                let body = self.block()?;
+                Ok(Stmt::While(cond, body))
+            }
+            Token::Return => {
+                self.idx += 1;
+                let value = if self.eat(";") {
+                    None
+                } else {
+                    let value = self.expr()?;
+                    self.expect(";")?;
+                    Some(value)
+                };
+                Ok(Stmt::Return(value))
+            }
+            Token::Ident(name) if self.tokens[self.idx + 1].0 == Token::Sym("=") => {
+                let name = name.clone();
+                let pos = self.pos();
+                self.idx += 2;
+                let value = self.expr()?;
+                self.expect(";")?;
+                Ok(Stmt::Assign(name, value, pos))
+            }
+            _ => {
+                let expr = self.expr()?;
+                self.expect(";")?;
+                Ok(Stmt::Expr(expr))
+            }
+        }
+    }
+
+    /// After the `if` keyword.
+    fn if_stmt(&mut self) -> Result<Stmt> {
+        let cond = self.expr()?;
+        let then = self.block()?;
+        let otherwise = if *self.peek() == Token::Else {
+            self.idx += 1;
+            if *self.peek() == Token::If {
+                self.idx += 1;
+                vec![self.if_stmt()?]
+            } else {
+                self.block()?
+            }
+        } else {
+            Vec::new()
+        };
+        Ok(Stmt::If(cond, then, otherwise))
+    }
+
+    fn expr(&mut self) -> Result<Expr> {
+        self.binary(0)
+    }
+
+    fn binary(&mut self, level: usize) -> Result<Expr> {
+        if level == LEVELS.len() {
+            return self.unary();
+        }
+
+        let mut lhs = self.binary(level + 1)?;
+        'outer: loop {
+            for (sym, op) in LEVELS[level] {
+                if self.eat(sym) {
+                    let rhs = self.binary(level + 1)?;
+                    lhs = Expr::Binary(*op, Box::new(lhs), Box::new(rhs));
+                    continue 'outer;
+                }
+            }
+            return Ok(lhs);
+        }
+    }
+
+    fn unary(&mut self) -> Result<Expr> {
+        if self.eat("-") {
+            Ok(Expr::Unary(UnOp::Neg, Box::new(self.unary()?)))
+        } else if self.eat("!") {
+            Ok(Expr::Unary(UnOp::Not, Box::new(self.unary()?)))
+        } else {
+            self.primary()
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr> {
+        let pos = self.pos();
+        match self.peek().clone() {
+            Token::Int(v) => {
+                self.idx += 1;
+                Ok(Expr::Int(v))
+            }
+            Token::Ident(name) => {
+                self.idx += 1;
+                if !self.eat("(") {
+                    return Ok(Expr::Var(name, pos));
+                }
+
+                let mut args = Vec::new();
+                if !self.eat(")") {
+                    loop {
+                        args.push(self.expr()?);
+                        if self.eat(")") {
+                            break;
+                        }
+                        self.expect(",")?;
+                    }
+                }
+                Ok(Expr::Call(name, args, pos))
+            }
+            Token::Sym("(") => {
+                self.idx += 1;
+                let expr = self.expr()?;
+                self.expect(")")?;
+                Ok(expr)
+            }
+            _ => self.unexpected("an expression"),
+        }
+    }
+}
+
+pub(super) fn parse(tokens: &[(Token, Pos)]) -> Result<Vec<Function>> {
+    let mut parser = Parser { tokens, idx: 0 };
+    let mut functions = Vec::new();
+
+    while *parser.peek() != Token::Eof {
+        functions.push(parser.function()?);
+    }
+    Ok(functions)
+}
@@ -1,153 +1,576 @@
+use std::cmp;
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+use std::sync::Arc;
+use std::time::Instant;
+
+pub mod aot;
+pub mod gdb;
+mod host;
+pub mod lang;
+mod limits;
+mod memory;
+mod opcode;
+mod patch;
+mod record;
+pub use host::HostCalls;
+pub use limits::Limits;
+use memory::Watches;
+pub use memory::{Change, Snapshot};
+use opcode::{Exec, Opcode};
+pub use opcode::{Param, BUILTIN_OPCODES};
+pub use patch::{Applied, Patch, Patches, Poke};
+pub use record::{Divergence, Exchange, Recorder, Transcript};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    UnknownOpcode(i64),
+    InvalidMode(i64),
+    InvalidOutputMode(i64),
+    InvalidAddress(i64),
+    Overflow,
+    ReservedOpcode(i64),
+    UnknownHostCall(i64),
+    InstructionLimit,
+    MemoryLimit(usize),
+    OutputLimit,
+    Deadline,
+    OutOfBounds(usize),
+    UnexpectedValue {
+        addr: usize,
+        expected: i64,
+        actual: i64,
+    },
+    Custom(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownOpcode(opcode) => write!(f, "unknown opcode {}", opcode),
+            Self::InvalidMode(mode) => write!(f, "invalid mode {}", mode),
+            Self::InvalidOutputMode(mode) => write!(f, "invalid output mode {}", mode),
+            Self::InvalidAddress(addr) => write!(f, "invalid address {}", addr),
+            Self::Overflow => write!(f, "arithmetic overflow"),
+            Self::ReservedOpcode(opcode) => write!(f, "opcode {} cannot be registered", opcode),
+            Self::UnknownHostCall(number) => write!(f, "unknown host call {}", number),
+            Self::InstructionLimit => write!(f, "instruction limit reached"),
+            Self::MemoryLimit(addr) => write!(f, "memory limit reached by address {}", addr),
+            Self::OutputLimit => write!(f, "output limit reached"),
+            Self::Deadline => write!(f, "deadline exceeded"),
+            Self::OutOfBounds(addr) => write!(f, "address {} out of bounds", addr),
+            Self::UnexpectedValue {
+                addr,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "expected {} at address {}, found {}",
+                expected, addr, actual
+            ),
+            Self::Custom(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Result of `Intcode::try_step`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Step {
+    /// An instruction was executed, consuming the input and producing
+    /// an output or not.
+    Executed {
+        consumed_input: bool,
+        output: Option<i64>,
+    },
+    /// Nothing was executed, the next instruction requires an input.
+    WaitingForInput,
+    /// The program halted.
+    Halted,
+}
+
 #[derive(Clone)]
 pub struct Intcode {
     pub memory: Vec<i64>,
     eip: usize,
     is_done: bool,
     rel_base: i64,
+    opcodes: HashMap<i64, Arc<Opcode>>,
+    limits: Limits,
+    /// Whether `limits` restricts instructions or time, which must
+    /// then be checked before each instruction.
+    checks_limits: bool,
+    nb_instructions: u64,
+    nb_outputs: usize,
+    /// Outputs of a run interrupted by an error, not yet returned.
+    pending_outputs: Vec<i64>,
+    watches: Watches,
 }
 
 impl Intcode {
-    pub fn new(state: &Vec<i64>) -> Self {
+    pub fn new(state: &[i64]) -> Self {
         Self {
-            memory: state.clone(),
+            memory: state.to_vec(),
             eip: 0,
             is_done: false,
             rel_base: 0,
+            opcodes: HashMap::new(),
+            limits: Limits::default(),
+            checks_limits: false,
+            nb_instructions: 0,
+            nb_outputs: 0,
+            pending_outputs: Vec::new(),
+            watches: Watches::new(),
         }
     }
 
-    fn get_param_val_and_mode(&mut self, instruction: &mut i64) -> (i64, i64) {
-        let v = self.memory[self.eip];
+    /// Restrict the resources the program can use.
+    ///
+    /// When the instruction, output or deadline limits are reached,
+    /// the instruction is not executed, and the program can be resumed
+    /// after raising the limits. Outputs produced by the interrupted
+    /// run are kept, see `take_pending_outputs`.
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.checks_limits = limits.max_instructions.is_some() || limits.deadline.is_some();
+        self.limits = limits;
+    }
+
+    pub fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    /// Number of instructions executed since the program started.
+    pub fn nb_instructions(&self) -> u64 {
+        self.nb_instructions
+    }
+
+    /// Register an additional opcode.
+    ///
+    /// `params` gives the arity of the opcode and which parameters are
+    /// write targets. When executed, `exec` receives the values of the
+    /// read parameters, and the current values of the write targets,
+    /// which it can modify to store results.
+    ///
+    /// Builtin opcodes cannot be overridden, but a registered opcode
+    /// can be replaced.
+    pub fn register_opcode<F>(&mut self, opcode: i64, params: &[Param], exec: F) -> Result<()>
+    where
+        F: Fn(&mut [i64]) -> Result<()> + Send + Sync + 'static,
+    {
+        opcode::check_registrable(opcode)?;
+        self.opcodes
+            .insert(opcode, Arc::new(Opcode::new(params, exec)));
+        Ok(())
+    }
+
+    /// Use `opcode` to call the functions registered in `calls`, see
+    /// `HostCalls`.
+    ///
+    /// This replaces any opcode previously registered with the same
+    /// number.
+    pub fn enable_host_calls(&mut self, opcode: i64, calls: HostCalls) -> Result<()> {
+        opcode::check_registrable(opcode)?;
+        self.opcodes
+            .insert(opcode, Arc::new(Opcode::host_calls(calls)));
+        Ok(())
+    }
+
+    fn get_param_val_and_mode(&mut self, instruction: &mut i64) -> Result<(i64, i64)> {
+        let v = self.get_memory_at(self.eip)?;
         self.eip += 1;
 
         let mode = *instruction % 10;
         *instruction /= 10;
 
-        (v, mode)
+        Ok((v, mode))
     }
 
-    fn ensure_memory_available(&mut self, pos: usize) {
+    /// Read the cells in `range`, which must be within the memory.
+    /// Otherwise, the error holds the first address out of it.
+    pub fn read(&self, range: Range<usize>) -> Result<&[i64]> {
+        let start = range.start;
+        self.memory
+            .get(range)
+            .ok_or_else(|| Error::OutOfBounds(cmp::max(start, self.memory.len())))
+    }
+
+    /// Write `values` from `addr`, which must be within the memory.
+    ///
+    /// Watches are not notified, they only track the writes done by the
+    /// program.
+    pub fn write(&mut self, addr: usize, values: &[i64]) -> Result<()> {
+        let end = addr.saturating_add(values.len());
+        match self.memory.get_mut(addr..end) {
+            Some(cells) => {
+                cells.copy_from_slice(values);
+                Ok(())
+            }
+            None => Err(Error::OutOfBounds(cmp::max(addr, self.memory.len()))),
+        }
+    }
+
+    /// Addresses at which `pattern` is found in memory, `None` matching
+    /// any value.
+    pub fn find(&self, pattern: &[Option<i64>]) -> Vec<usize> {
+        if pattern.is_empty() {
+            return Vec::new();
+        }
+        self.memory
+            .windows(pattern.len())
+            .enumerate()
+            .filter(|(_, cells)| {
+                cells
+                    .iter()
+                    .zip(pattern)
+                    .all(|(cell, p)| p.is_none_or(|p| p == *cell))
+            })
+            .map(|(addr, _)| addr)
+            .collect()
+    }
+
+    /// Copy the memory, to compare it later with `Snapshot::diff`.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot::new(&self.memory)
+    }
+
+    /// Call `f` on every write done by the program at `addr`, including
+    /// writes leaving the value unchanged. Clones share their watches.
+    pub fn watch<F>(&mut self, addr: usize, f: F)
+    where
+        F: Fn(&Change) + Send + Sync + 'static,
+    {
+        self.watches.entry(addr).or_default().push(Arc::new(f));
+    }
+
+    /// Remove the watches on `addr`.
+    pub fn unwatch(&mut self, addr: usize) {
+        self.watches.remove(&addr);
+    }
+
+    fn ensure_memory_available(&mut self, pos: usize) -> Result<()> {
         if pos >= self.memory.len() {
+            if let Some(max) = self.limits.max_memory {
+                if pos >= max {
+                    return Err(Error::MemoryLimit(pos));
+                }
+            }
+            // do not abort on absurd addresses, the program is invalid
+            if self
+                .memory
+                .try_reserve(pos + 1 - self.memory.len())
+                .is_err()
+            {
+                return Err(Error::InvalidAddress(pos as i64));
+            }
             self.memory.resize(pos + 1, 0);
         }
+        Ok(())
+    }
+
+    fn get_memory_at(&mut self, pos: usize) -> Result<i64> {
+        self.ensure_memory_available(pos)?;
+        Ok(self.memory[pos])
+    }
+
+    /// Write done by the program, notifying the watches.
+    fn set_memory_at(&mut self, pos: usize, value: i64) -> Result<()> {
+        self.ensure_memory_available(pos)?;
+        let old = std::mem::replace(&mut self.memory[pos], value);
+
+        if !self.watches.is_empty() {
+            if let Some(watches) = self.watches.get(&pos) {
+                let change = Change {
+                    addr: pos,
+                    old,
+                    new: value,
+                };
+                for watch in watches {
+                    watch(&change);
+                }
+            }
+        }
+        Ok(())
     }
 
-    fn get_memory_at(&mut self, pos: usize) -> i64 {
-        self.ensure_memory_available(pos);
-        self.memory[pos]
+    fn to_address(v: i64) -> Result<usize> {
+        if v < 0 {
+            Err(Error::InvalidAddress(v))
+        } else {
+            Ok(v as usize)
+        }
     }
 
-    fn get_memory_at_mut(&mut self, pos: usize) -> &mut i64 {
-        self.ensure_memory_available(pos);
-        &mut self.memory[pos]
+    fn relative_address(&self, v: i64) -> Result<usize> {
+        let addr = v.checked_add(self.rel_base).ok_or(Error::Overflow)?;
+        Self::to_address(addr)
     }
-    
-    fn get_param_value(&mut self, instruction: &mut i64) -> i64 {
-        let (v, mode) = self.get_param_val_and_mode(instruction);
+
+    fn get_param_value(&mut self, instruction: &mut i64) -> Result<i64> {
+        let (v, mode) = self.get_param_val_and_mode(instruction)?;
 
         match mode {
             // position mode
-            0 => self.get_memory_at(v as usize),
+            0 => self.get_memory_at(Self::to_address(v)?),
             // immediate mode
-            1 => v,
-            2 => self.get_memory_at((v + self.rel_base) as usize),
-            _ => panic!("invalid mode {}", mode),
+            1 => Ok(v),
+            2 => self.get_memory_at(self.relative_address(v)?),
+            _ => Err(Error::InvalidMode(mode)),
         }
     }
 
-    fn get_outptr<'a>(&'a mut self, instruction: &mut i64) -> &'a mut i64 {
-        let (v, mode) = self.get_param_val_and_mode(instruction);
+    fn get_outaddr(&mut self, instruction: &mut i64) -> Result<usize> {
+        let (v, mode) = self.get_param_val_and_mode(instruction)?;
 
         match mode {
             // position mode
-            0 => self.get_memory_at_mut(v as usize),
-            2 => self.get_memory_at_mut((v + self.rel_base) as usize),
-            _ => panic!("invalid output mode {}", mode),
+            0 => Self::to_address(v),
+            2 => self.relative_address(v),
+            _ => Err(Error::InvalidOutputMode(mode)),
         }
     }
 
+    fn exec_opcode(&mut self, def: &Opcode, instruction: &mut i64) -> Result<()> {
+        let exec = match &def.exec {
+            Exec::Func(exec) => exec,
+            Exec::HostCalls(calls) => return self.exec_host_call(calls, instruction),
+        };
+        let mut args = Vec::with_capacity(def.params.len());
+        let mut outaddrs = Vec::new();
+
+        for (idx, param) in def.params.iter().enumerate() {
+            match param {
+                Param::In => args.push(self.get_param_value(instruction)?),
+                Param::Out => {
+                    let addr = self.get_outaddr(instruction)?;
+                    args.push(self.get_memory_at(addr)?);
+                    outaddrs.push((idx, addr));
+                }
+            }
+        }
+
+        exec(&mut args)?;
+
+        for (idx, addr) in outaddrs {
+            self.set_memory_at(addr, args[idx])?;
+        }
+        Ok(())
+    }
+
+    fn exec_host_call(&mut self, calls: &HostCalls, instruction: &mut i64) -> Result<()> {
+        let number = self.get_param_value(instruction)?;
+        let args_addr = self.get_outaddr(instruction)?;
+        let results_addr = self.get_outaddr(instruction)?;
+
+        let results = calls.call(number, |idx| self.get_memory_at(args_addr + idx))?;
+        for (idx, v) in results.into_iter().enumerate() {
+            self.set_memory_at(results_addr + idx, v)?;
+        }
+        Ok(())
+    }
+
+    fn check_limits(&self) -> Result<()> {
+        if self
+            .limits
+            .max_instructions
+            .is_some_and(|max| self.nb_instructions >= max)
+        {
+            return Err(Error::InstructionLimit);
+        }
+        if self
+            .nb_instructions
+            .is_multiple_of(limits::DEADLINE_CHECK_PERIOD)
+        {
+            if let Some(deadline) = self.limits.deadline {
+                if Instant::now() >= deadline {
+                    return Err(Error::Deadline);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Run the program until it halts or requires more inputs than
+    /// provided, and return the outputs produced.
+    ///
+    /// Panics if the program is invalid, see `try_run` for a
+    /// non-panicking version.
     pub fn run(&mut self, inputs: &[i64]) -> Vec<i64> {
+        match self.try_run(inputs) {
+            Ok(output) => output,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Same as `run`, but returns an error if the program is invalid,
+    /// or exceeds its limits.
+    ///
+    /// Once an error has been returned, the program cannot be resumed,
+    /// unless stated otherwise in `set_limits`.
+    pub fn try_run(&mut self, inputs: &[i64]) -> Result<Vec<i64>> {
         let mut input_pos = 0;
-        let mut output = Vec::new();
+        let mut output = std::mem::take(&mut self.pending_outputs);
 
         loop {
-            let mut instruction = self.memory[self.eip];
-            self.eip += 1;
-
-            let opcode = instruction % 100;
-            instruction /= 100;
-
-            match opcode % 100 {
-                1 => {
-                    let in1 = self.get_param_value(&mut instruction);
-                    let in2 = self.get_param_value(&mut instruction);
-                    let out = self.get_outptr(&mut instruction);
-                    *out = in1 + in2;
-                }
-                2 => {
-                    let in1 = self.get_param_value(&mut instruction);
-                    let in2 = self.get_param_value(&mut instruction);
-                    let out = self.get_outptr(&mut instruction);
-                    *out = in1 * in2;
-                }
-                3 => {
-                    if input_pos >= inputs.len() {
-                        /* rewind eip so that execution can be resumed */
-                        self.eip -= 1;
-                        break;
-                    }
-                    let out = self.get_outptr(&mut instruction);
-                    *out = inputs[input_pos];
-                    input_pos += 1;
-                }
-                4 => {
-                    let val = self.get_param_value(&mut instruction);
-                    output.push(val);
-                }
-                5 => {
-                    let val = self.get_param_value(&mut instruction);
-                    if val != 0 {
-                        self.eip = self.get_param_value(&mut instruction) as usize;
-                    } else {
-                        self.eip += 1;
-                    }
-                }
-                6 => {
-                    let val = self.get_param_value(&mut instruction);
-                    if val == 0 {
-                        self.eip = self.get_param_value(&mut instruction) as usize;
-                    } else {
-                        self.eip += 1;
-                    }
+            match self.exec_instruction(inputs, &mut input_pos, &mut output) {
+                Ok(true) => (),
+                Ok(false) => return Ok(output),
+                Err(e) => {
+                    self.pending_outputs = output;
+                    return Err(e);
                 }
-                7 => {
-                    let in1 = self.get_param_value(&mut instruction);
-                    let in2 = self.get_param_value(&mut instruction);
-                    let out = self.get_outptr(&mut instruction);
-                    *out = if in1 < in2 { 1 } else { 0 };
+            }
+        }
+    }
+
+    /// Outputs produced by runs interrupted by an error, which are
+    /// otherwise returned first by the next run.
+    pub fn take_pending_outputs(&mut self) -> Vec<i64> {
+        std::mem::take(&mut self.pending_outputs)
+    }
+
+    /// Execute a single instruction, with an optional input available.
+    pub fn try_step(&mut self, input: Option<i64>) -> Result<Step> {
+        let mut input_pos = 0;
+        let mut output = Vec::new();
+
+        if self.exec_instruction(input.as_slice(), &mut input_pos, &mut output)? {
+            Ok(Step::Executed {
+                consumed_input: input_pos > 0,
+                output: output.pop(),
+            })
+        } else if self.is_done {
+            Ok(Step::Halted)
+        } else {
+            Ok(Step::WaitingForInput)
+        }
+    }
+
+    /// Execute the instruction at `eip`. Returns false if execution
+    /// must stop, because the program halted or waits for an input.
+    #[inline]
+    fn exec_instruction(
+        &mut self,
+        inputs: &[i64],
+        input_pos: &mut usize,
+        output: &mut Vec<i64>,
+    ) -> Result<bool> {
+        if self.checks_limits {
+            self.check_limits()?;
+        }
+        self.nb_instructions += 1;
+
+        let mut instruction = self.get_memory_at(self.eip)?;
+        self.eip += 1;
+
+        let opcode = instruction % 100;
+        instruction /= 100;
+
+        match opcode {
+            1 => {
+                let in1 = self.get_param_value(&mut instruction)?;
+                let in2 = self.get_param_value(&mut instruction)?;
+                let out = self.get_outaddr(&mut instruction)?;
+                self.set_memory_at(out, in1.checked_add(in2).ok_or(Error::Overflow)?)?;
+            }
+            2 => {
+                let in1 = self.get_param_value(&mut instruction)?;
+                let in2 = self.get_param_value(&mut instruction)?;
+                let out = self.get_outaddr(&mut instruction)?;
+                self.set_memory_at(out, in1.checked_mul(in2).ok_or(Error::Overflow)?)?;
+            }
+            3 => {
+                if *input_pos >= inputs.len() {
+                    /* rewind eip so that execution can be resumed */
+                    self.eip -= 1;
+                    self.nb_instructions -= 1;
+                    return Ok(false);
                 }
-                8 => {
-                    let in1 = self.get_param_value(&mut instruction);
-                    let in2 = self.get_param_value(&mut instruction);
-                    let out = self.get_outptr(&mut instruction);
-                    *out = if in1 == in2 { 1 } else { 0 };
+                let out = self.get_outaddr(&mut instruction)?;
+                self.set_memory_at(out, inputs[*input_pos])?;
+                *input_pos += 1;
+            }
+            4 => {
+                if self
+                    .limits
+                    .max_outputs
+                    .is_some_and(|max| self.nb_outputs >= max)
+                {
+                    self.eip -= 1;
+                    self.nb_instructions -= 1;
+                    return Err(Error::OutputLimit);
                 }
-                9 => {
-                    let v = self.get_param_value(&mut instruction);
-                    self.rel_base += v;
+                let val = self.get_param_value(&mut instruction)?;
+                output.push(val);
+                self.nb_outputs += 1;
+            }
+            5 => {
+                let val = self.get_param_value(&mut instruction)?;
+                if val != 0 {
+                    let target = self.get_param_value(&mut instruction)?;
+                    self.eip = Self::to_address(target)?;
+                } else {
+                    self.eip += 1;
                 }
-                99 => {
-                    self.is_done = true;
-                    break;
+            }
+            6 => {
+                let val = self.get_param_value(&mut instruction)?;
+                if val == 0 {
+                    let target = self.get_param_value(&mut instruction)?;
+                    self.eip = Self::to_address(target)?;
+                } else {
+                    self.eip += 1;
                 }
-                _ => panic!("unknown opcode {}", opcode),
             }
+            7 => {
+                let in1 = self.get_param_value(&mut instruction)?;
+                let in2 = self.get_param_value(&mut instruction)?;
+                let out = self.get_outaddr(&mut instruction)?;
+                self.set_memory_at(out, if in1 < in2 { 1 } else { 0 })?;
+            }
+            8 => {
+                let in1 = self.get_param_value(&mut instruction)?;
+                let in2 = self.get_param_value(&mut instruction)?;
+                let out = self.get_outaddr(&mut instruction)?;
+                self.set_memory_at(out, if in1 == in2 { 1 } else { 0 })?;
+            }
+            9 => {
+                let v = self.get_param_value(&mut instruction)?;
+                self.rel_base = self.rel_base.checked_add(v).ok_or(Error::Overflow)?;
+            }
+            99 => {
+                self.is_done = true;
+                return Ok(false);
+            }
+            _ => match self.opcodes.get(&opcode).map(Arc::clone) {
+                Some(def) => self.exec_opcode(&def, &mut instruction)?,
+                None => return Err(Error::UnknownOpcode(opcode)),
+            },
         }
-
-        output
+        Ok(true)
     }
 
     pub fn is_done(&self) -> bool {
         self.is_done
     }
+
+    /// Address of the next instruction to execute.
+    pub fn eip(&self) -> usize {
+        self.eip
+    }
+
+    pub fn set_eip(&mut self, eip: usize) {
+        self.eip = eip;
+    }
+
+    pub fn rel_base(&self) -> i64 {
+        self.rel_base
+    }
+
+    pub fn set_rel_base(&mut self, rel_base: i64) {
+        self.rel_base = rel_base;
+    }
 }
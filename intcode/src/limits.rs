@@ -0,0 +1,25 @@
+use std::time::Instant;
+
+/// Resource limits for programs that cannot be trusted, see
+/// `Intcode::set_limits`.
+///
+/// Each limit exceeded stops the execution with its own error.
+#[derive(Debug, Clone, Default)]
+pub struct Limits {
+    /// Maximum number of instructions executed, over all runs.
+    /// Exceeding it returns `Error::InstructionLimit`.
+    pub max_instructions: Option<u64>,
+    /// Maximum number of memory cells. Accessing an address past it
+    /// returns `Error::MemoryLimit`.
+    pub max_memory: Option<usize>,
+    /// Maximum number of values output, over all runs. Exceeding it
+    /// returns `Error::OutputLimit`.
+    pub max_outputs: Option<usize>,
+    /// Instant after which execution is stopped with
+    /// `Error::Deadline`.
+    pub deadline: Option<Instant>,
+}
+
+/// The clock is only checked every so many instructions, as it is
+/// way more costly than executing one.
+pub(crate) const DEADLINE_CHECK_PERIOD: u64 = 1024;
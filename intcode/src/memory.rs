@@ -0,0 +1,51 @@
+use std::cmp;
+use std::sync::Arc;
+
+/// Change of a memory cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Change {
+    pub addr: usize,
+    pub old: i64,
+    pub new: i64,
+}
+
+pub(crate) type WatchFn = dyn Fn(&Change) + Send + Sync;
+
+pub(crate) type Watches = std::collections::HashMap<usize, Vec<Arc<WatchFn>>>;
+
+/// Copy of the memory at some point, see `Intcode::snapshot`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    memory: Vec<i64>,
+}
+
+impl Snapshot {
+    pub(crate) fn new(memory: &[i64]) -> Self {
+        Self {
+            memory: memory.to_vec(),
+        }
+    }
+
+    pub fn memory(&self) -> &[i64] {
+        &self.memory
+    }
+
+    /// List the cells that differ in `newer`, by increasing address.
+    /// Cells beyond the end of a snapshot count as 0, as memory is
+    /// zero-initialized when it grows.
+    pub fn diff(&self, newer: &Snapshot) -> Vec<Change> {
+        let len = cmp::max(self.memory.len(), newer.memory.len());
+
+        (0..len)
+            .filter_map(|addr| {
+                let old = self.memory.get(addr).cloned().unwrap_or(0);
+                let new = newer.memory.get(addr).cloned().unwrap_or(0);
+                if old != new {
+                    Some(Change { addr, old, new })
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,56 @@
+use crate::{Error, HostCalls, Result};
+use std::sync::Arc;
+
+/// Kind of a parameter of a custom opcode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Param {
+    /// Read parameter, in position, immediate or relative mode.
+    In,
+    /// Write target, in position or relative mode.
+    Out,
+}
+
+type ExecFn = dyn Fn(&mut [i64]) -> Result<()> + Send + Sync;
+
+#[derive(Clone)]
+pub(crate) enum Exec {
+    Func(Arc<ExecFn>),
+    HostCalls(Arc<HostCalls>),
+}
+
+/// Definition of an opcode registered with `Intcode::register_opcode`.
+#[derive(Clone)]
+pub(crate) struct Opcode {
+    pub(crate) params: Vec<Param>,
+    pub(crate) exec: Exec,
+}
+
+impl Opcode {
+    pub(crate) fn new<F>(params: &[Param], exec: F) -> Self
+    where
+        F: Fn(&mut [i64]) -> Result<()> + Send + Sync + 'static,
+    {
+        Self {
+            params: params.to_vec(),
+            exec: Exec::Func(Arc::new(exec)),
+        }
+    }
+
+    pub(crate) fn host_calls(calls: HostCalls) -> Self {
+        Self {
+            params: vec![Param::In, Param::Out, Param::Out],
+            exec: Exec::HostCalls(Arc::new(calls)),
+        }
+    }
+}
+
+/// Opcodes implemented by the VM itself, which cannot be overridden.
+pub const BUILTIN_OPCODES: [i64; 10] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 99];
+
+pub(crate) fn check_registrable(opcode: i64) -> Result<()> {
+    if !(0..100).contains(&opcode) || BUILTIN_OPCODES.contains(&opcode) {
+        Err(Error::ReservedOpcode(opcode))
+    } else {
+        Ok(())
+    }
+}
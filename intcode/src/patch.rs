@@ -0,0 +1,222 @@
+use crate::{Error, Intcode, Result};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Write of `value` at `addr`, which must hold `expected` if set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Poke {
+    pub addr: usize,
+    pub value: i64,
+    pub expected: Option<i64>,
+}
+
+/// List of pokes applied together.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Patch {
+    pub name: Option<String>,
+    pub pokes: Vec<Poke>,
+}
+
+/// Patch applied by `Patch::apply`, holding the values it replaced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Applied {
+    originals: Vec<(usize, i64)>,
+}
+
+impl Applied {
+    /// Restore the values replaced by the patch, in reverse order so
+    /// that an address poked twice gets its original value back.
+    pub fn revert(self, intcode: &mut Intcode) -> Result<()> {
+        for (addr, value) in self.originals.into_iter().rev() {
+            intcode.write(addr, &[value])?;
+        }
+        Ok(())
+    }
+}
+
+impl Patch {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: Some(name.to_owned()),
+            pokes: Vec::new(),
+        }
+    }
+
+    /// Add a poke of `value` at `addr`.
+    pub fn poke(&mut self, addr: usize, value: i64) {
+        self.pokes.push(Poke {
+            addr,
+            value,
+            expected: None,
+        });
+    }
+
+    /// Add a poke of `value` at `addr`, which must hold `expected`.
+    pub fn poke_expecting(&mut self, addr: usize, expected: i64, value: i64) {
+        self.pokes.push(Poke {
+            addr,
+            value,
+            expected: Some(expected),
+        });
+    }
+
+    /// Check that every address is in memory and holds its expected
+    /// value.
+    pub fn validate(&self, intcode: &Intcode) -> Result<()> {
+        for poke in &self.pokes {
+            let end = poke
+                .addr
+                .checked_add(1)
+                .ok_or(Error::OutOfBounds(poke.addr))?;
+            let actual = intcode.read(poke.addr..end)?[0];
+            match poke.expected {
+                Some(expected) if expected != actual => {
+                    return Err(Error::UnexpectedValue {
+                        addr: poke.addr,
+                        expected,
+                        actual,
+                    })
+                }
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+
+    /// Validate then apply the patch. Nothing is written if the
+    /// validation fails.
+    pub fn apply(&self, intcode: &mut Intcode) -> Result<Applied> {
+        self.validate(intcode)?;
+
+        let mut originals = Vec::with_capacity(self.pokes.len());
+        for poke in &self.pokes {
+            originals.push((poke.addr, intcode.memory[poke.addr]));
+            intcode.write(poke.addr, &[poke.value])?;
+        }
+        Ok(Applied { originals })
+    }
+}
+
+/// Patches described in a text file:
+///
+/// ```text
+/// # noun and verb of day 2
+/// [1202]
+/// 1: 12
+/// 2: 2
+///
+/// [free-play]
+/// 0: 1 -> 2
+/// ```
+///
+/// Each patch starts with its name in brackets, pokes before the first
+/// name forming an unnamed patch. A poke is written `addr: value`, or
+/// `addr: expected -> value` to check the original value.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Patches {
+    pub patches: Vec<Patch>,
+}
+
+impl Patches {
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        fs::read_to_string(path)?
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Patch> {
+        self.patches
+            .iter()
+            .find(|patch| patch.name.as_deref() == Some(name))
+    }
+}
+
+impl fmt::Display for Patches {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, patch) in self.patches.iter().enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+            if let Some(name) = &patch.name {
+                writeln!(f, "[{}]", name)?;
+            }
+            for poke in &patch.pokes {
+                match poke.expected {
+                    Some(expected) => writeln!(f, "{}: {} -> {}", poke.addr, expected, poke.value)?,
+                    None => writeln!(f, "{}: {}", poke.addr, poke.value)?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_poke(line: &str) -> std::result::Result<Poke, String> {
+    let mut parts = line.splitn(2, ':');
+    let addr = parts.next().unwrap().trim();
+    let values = parts.next().ok_or("missing `:`")?;
+    let addr = addr
+        .parse()
+        .map_err(|e| format!("invalid address `{}`: {}", addr, e))?;
+    let parse = |s: &str| {
+        s.trim()
+            .parse::<i64>()
+            .map_err(|e| format!("invalid value `{}`: {}", s.trim(), e))
+    };
+
+    let mut values = values.splitn(2, "->");
+    let first = parse(values.next().unwrap())?;
+    Ok(match values.next() {
+        Some(value) => Poke {
+            addr,
+            value: parse(value)?,
+            expected: Some(first),
+        },
+        None => Poke {
+            addr,
+            value: first,
+            expected: None,
+        },
+    })
+}
+
+impl FromStr for Patches {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let mut patches: Vec<Patch> = Vec::new();
+
+        for (idx, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if line.starts_with('[') {
+                let name = line
+                    .strip_prefix('[')
+                    .and_then(|l| l.strip_suffix(']'))
+                    .ok_or_else(|| format!("line {}: missing `]`", idx + 1))?;
+                patches.push(Patch::new(name.trim()));
+                continue;
+            }
+
+            let poke = parse_poke(line).map_err(|e| format!("line {}: {}", idx + 1, e))?;
+            match patches.last_mut() {
+                Some(patch) => patch.pokes.push(poke),
+                None => patches.push(Patch {
+                    name: None,
+                    pokes: vec![poke],
+                }),
+            }
+        }
+        Ok(Self { patches })
+    }
+}
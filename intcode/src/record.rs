@@ -0,0 +1,242 @@
+use crate::{Error, Intcode, Result};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+/// One call to `Intcode::run`: the inputs given, and the outputs
+/// produced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Exchange {
+    pub inputs: Vec<i64>,
+    pub outputs: Vec<i64>,
+}
+
+/// Input/output transcript of a session.
+///
+/// It is saved as text, one exchange per line, with a final `halted`
+/// line if the program ran to completion:
+///
+/// ```text
+/// 1 -> 0,0,1
+/// -1,0 -> 5
+/// halted
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Transcript {
+    pub exchanges: Vec<Exchange>,
+    pub halted: bool,
+}
+
+/// First difference found when replaying a transcript.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Divergence {
+    /// Output `index` of exchange `exchange` differs, `None` meaning
+    /// the output is missing.
+    Output {
+        exchange: usize,
+        index: usize,
+        expected: Option<i64>,
+        actual: Option<i64>,
+    },
+    /// The program failed during exchange `exchange`.
+    Error { exchange: usize, error: Error },
+    /// The program did not halt when expected, or the reverse.
+    Halted { expected: bool, actual: bool },
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |v: &Option<i64>| match v {
+            Some(v) => v.to_string(),
+            None => "nothing".to_owned(),
+        };
+
+        match self {
+            Self::Output {
+                exchange,
+                index,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "exchange {}, output {}: expected {}, got {}",
+                exchange,
+                index,
+                show(expected),
+                show(actual)
+            ),
+            Self::Error { exchange, error } => write!(f, "exchange {}: {}", exchange, error),
+            Self::Halted { expected, actual } => write!(
+                f,
+                "program {} but was expected to{}",
+                if *actual { "halted" } else { "did not halt" },
+                if *expected { "" } else { " wait for inputs" },
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Divergence {}
+
+impl Transcript {
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        fs::read_to_string(path)?
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    /// Run `intcode` with the recorded inputs, checking that it
+    /// produces the recorded outputs.
+    pub fn replay(&self, mut intcode: Intcode) -> std::result::Result<(), Divergence> {
+        for (exchange, ex) in self.exchanges.iter().enumerate() {
+            let outputs = intcode
+                .try_run(&ex.inputs)
+                .map_err(|error| Divergence::Error { exchange, error })?;
+
+            let len = std::cmp::max(outputs.len(), ex.outputs.len());
+            for index in 0..len {
+                let expected = ex.outputs.get(index).cloned();
+                let actual = outputs.get(index).cloned();
+                if expected != actual {
+                    return Err(Divergence::Output {
+                        exchange,
+                        index,
+                        expected,
+                        actual,
+                    });
+                }
+            }
+        }
+
+        if intcode.is_done() != self.halted {
+            return Err(Divergence::Halted {
+                expected: self.halted,
+                actual: intcode.is_done(),
+            });
+        }
+        Ok(())
+    }
+}
+
+fn format_values(f: &mut fmt::Formatter<'_>, values: &[i64]) -> fmt::Result {
+    for (idx, v) in values.iter().enumerate() {
+        if idx > 0 {
+            write!(f, ",")?;
+        }
+        write!(f, "{}", v)?;
+    }
+    Ok(())
+}
+
+fn parse_values(s: &str) -> std::result::Result<Vec<i64>, std::num::ParseIntError> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Ok(Vec::new());
+    }
+    s.split(',').map(|v| v.trim().parse::<i64>()).collect()
+}
+
+impl fmt::Display for Transcript {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for ex in &self.exchanges {
+            format_values(f, &ex.inputs)?;
+            write!(f, " -> ")?;
+            format_values(f, &ex.outputs)?;
+            writeln!(f)?;
+        }
+        if self.halted {
+            writeln!(f, "halted")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Transcript {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let mut transcript = Self::default();
+
+        for (idx, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if transcript.halted {
+                return Err(format!("line {}: exchange after halt", idx + 1));
+            }
+            if line == "halted" {
+                transcript.halted = true;
+                continue;
+            }
+
+            let mut parts = line.splitn(2, "->");
+            let inputs = parts.next().unwrap();
+            let outputs = parts
+                .next()
+                .ok_or_else(|| format!("line {}: missing `->`", idx + 1))?;
+            let parse = |s| parse_values(s).map_err(|e| format!("line {}: {}", idx + 1, e));
+
+            transcript.exchanges.push(Exchange {
+                inputs: parse(inputs)?,
+                outputs: parse(outputs)?,
+            });
+        }
+        Ok(transcript)
+    }
+}
+
+/// Wrapper around `Intcode` recording every exchange.
+pub struct Recorder {
+    intcode: Intcode,
+    transcript: Transcript,
+}
+
+impl Recorder {
+    pub fn new(intcode: Intcode) -> Self {
+        Self {
+            intcode,
+            transcript: Transcript::default(),
+        }
+    }
+
+    pub fn run(&mut self, inputs: &[i64]) -> Vec<i64> {
+        match self.try_run(inputs) {
+            Ok(output) => output,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    pub fn try_run(&mut self, inputs: &[i64]) -> Result<Vec<i64>> {
+        let outputs = self.intcode.try_run(inputs)?;
+
+        self.transcript.exchanges.push(Exchange {
+            inputs: inputs.to_vec(),
+            outputs: outputs.clone(),
+        });
+        self.transcript.halted = self.intcode.is_done();
+        Ok(outputs)
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.intcode.is_done()
+    }
+
+    pub fn intcode(&self) -> &Intcode {
+        &self.intcode
+    }
+
+    pub fn transcript(&self) -> &Transcript {
+        &self.transcript
+    }
+
+    pub fn into_transcript(self) -> Transcript {
+        self.transcript
+    }
+}
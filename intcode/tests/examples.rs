@@ -0,0 +1,160 @@
+use intcode::{Error, Intcode};
+
+fn run_program(memory: &[i64], inputs: &[i64]) -> Vec<i64> {
+    Intcode::new(memory).run(inputs)
+}
+
+fn run_memory(memory: &[i64]) -> Vec<i64> {
+    let mut intcode = Intcode::new(memory);
+    intcode.run(&[]);
+    assert!(intcode.is_done());
+    intcode.memory
+}
+
+// {{{ Day 2
+
+#[test]
+fn day2_add_mul() {
+    assert_eq!(
+        run_memory(&[1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50]),
+        [3500, 9, 10, 70, 2, 3, 11, 0, 99, 30, 40, 50]
+    );
+    assert_eq!(run_memory(&[1, 0, 0, 0, 99]), [2, 0, 0, 0, 99]);
+    assert_eq!(run_memory(&[2, 3, 0, 3, 99]), [2, 3, 0, 6, 99]);
+    assert_eq!(run_memory(&[2, 4, 4, 5, 99, 0]), [2, 4, 4, 5, 99, 9801]);
+    assert_eq!(
+        run_memory(&[1, 1, 1, 4, 99, 5, 6, 0, 99]),
+        [30, 1, 1, 4, 2, 5, 6, 0, 99]
+    );
+}
+
+// }}}
+// {{{ Day 5
+
+#[test]
+fn day5_io() {
+    assert_eq!(run_program(&[3, 0, 4, 0, 99], &[42]), [42]);
+    assert_eq!(run_memory(&[1002, 4, 3, 4, 33]), [1002, 4, 3, 4, 99]);
+    assert_eq!(run_memory(&[1101, 100, -1, 4, 0]), [1101, 100, -1, 4, 99]);
+}
+
+#[test]
+fn day5_comparisons() {
+    let eq8_pos = [3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8];
+    let lt8_pos = [3, 9, 7, 9, 10, 9, 4, 9, 99, -1, 8];
+    let eq8_imm = [3, 3, 1108, -1, 8, 3, 4, 3, 99];
+    let lt8_imm = [3, 3, 1107, -1, 8, 3, 4, 3, 99];
+
+    for input in 6..11 {
+        let eq = if input == 8 { 1 } else { 0 };
+        let lt = if input < 8 { 1 } else { 0 };
+
+        assert_eq!(run_program(&eq8_pos, &[input]), [eq]);
+        assert_eq!(run_program(&lt8_pos, &[input]), [lt]);
+        assert_eq!(run_program(&eq8_imm, &[input]), [eq]);
+        assert_eq!(run_program(&lt8_imm, &[input]), [lt]);
+    }
+}
+
+#[test]
+fn day5_jumps() {
+    let jmp_pos = [3, 12, 6, 12, 15, 1, 13, 14, 13, 4, 13, 99, -1, 0, 1, 9];
+    let jmp_imm = [3, 3, 1105, -1, 9, 1101, 0, 0, 12, 4, 12, 99, 1];
+
+    for input in -1..2 {
+        let expected = if input == 0 { 0 } else { 1 };
+
+        assert_eq!(run_program(&jmp_pos, &[input]), [expected]);
+        assert_eq!(run_program(&jmp_imm, &[input]), [expected]);
+    }
+}
+
+#[test]
+fn day5_compare_to_8() {
+    let pgm = [
+        3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0, 0,
+        1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4, 20,
+        1105, 1, 46, 98, 99,
+    ];
+
+    assert_eq!(run_program(&pgm, &[7]), [999]);
+    assert_eq!(run_program(&pgm, &[8]), [1000]);
+    assert_eq!(run_program(&pgm, &[9]), [1001]);
+}
+
+// }}}
+// {{{ Day 9
+
+#[test]
+fn day9_quine() {
+    let pgm = [
+        109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+    ];
+
+    assert_eq!(run_program(&pgm, &[]), pgm);
+}
+
+#[test]
+fn day9_large_numbers() {
+    let out = run_program(&[1102, 34915192, 34915192, 7, 4, 7, 99, 0], &[]);
+    assert_eq!(out.len(), 1);
+    assert_eq!(out[0].to_string().len(), 16);
+
+    assert_eq!(
+        run_program(&[104, 1125899906842624, 99], &[]),
+        [1125899906842624]
+    );
+}
+
+#[test]
+fn day9_relative_base() {
+    // 109,19 then 204,-34 reads the value at 19 - 34 + 2000
+    let mut pgm = vec![109, 2000, 109, 19, 204, -34, 99];
+    pgm.resize(1986, 0);
+    pgm[1985] = 1337;
+
+    assert_eq!(run_program(&pgm, &[]), [1337]);
+}
+
+// }}}
+// {{{ Resume & errors
+
+#[test]
+fn resume_on_missing_input() {
+    let mut intcode = Intcode::new(&[3, 11, 3, 12, 1, 11, 12, 13, 4, 13, 99]);
+
+    assert_eq!(intcode.run(&[3]), []);
+    assert!(!intcode.is_done());
+    assert_eq!(intcode.run(&[4]), [7]);
+    assert!(intcode.is_done());
+}
+
+#[test]
+fn errors() {
+    let try_run = |memory: &[i64]| Intcode::new(memory).try_run(&[]);
+
+    assert_eq!(try_run(&[42]), Err(Error::UnknownOpcode(42)));
+    assert_eq!(try_run(&[301, 0, 0, 0, 99]), Err(Error::InvalidMode(3)));
+    assert_eq!(
+        try_run(&[11101, 0, 0, 0, 99]),
+        Err(Error::InvalidOutputMode(1))
+    );
+    assert_eq!(try_run(&[4, -1, 99]), Err(Error::InvalidAddress(-1)));
+    assert_eq!(try_run(&[204, -1, 99]), Err(Error::InvalidAddress(-1)));
+    assert_eq!(try_run(&[1105, 1, -3, 99]), Err(Error::InvalidAddress(-3)));
+    assert_eq!(try_run(&[1102, i64::MAX, 2, 0, 99]), Err(Error::Overflow));
+    assert_eq!(
+        try_run(&[4, i64::MAX, 99]),
+        Err(Error::InvalidAddress(i64::MAX))
+    );
+    // running past the end of the program reads zeroes
+    assert_eq!(try_run(&[1101, 0, 0, 0]), Err(Error::UnknownOpcode(0)));
+}
+
+#[test]
+#[should_panic(expected = "unknown opcode 42")]
+fn run_panics_on_error() {
+    Intcode::new(&[42]).run(&[]);
+}
+
+// }}}
@@ -0,0 +1,175 @@
+use intcode::gdb::Server;
+use intcode::{Intcode, Limits};
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+
+/// Minimal scripted GDB client.
+struct Client {
+    stream: TcpStream,
+}
+
+impl Client {
+    fn read_byte(&mut self) -> u8 {
+        let mut b = [0];
+        self.stream.read_exact(&mut b).unwrap();
+        b[0]
+    }
+
+    fn send(&mut self, data: &str) -> String {
+        let checksum = data.bytes().fold(0u8, |acc, b| acc.wrapping_add(b));
+        write!(self.stream, "${}#{:02x}", data, checksum).unwrap();
+        assert_eq!(self.read_byte(), b'+');
+        self.receive()
+    }
+
+    fn receive(&mut self) -> String {
+        assert_eq!(self.read_byte(), b'$');
+        let mut reply = Vec::new();
+        loop {
+            match self.read_byte() {
+                b'#' => break,
+                b => reply.push(b),
+            }
+        }
+        self.read_byte();
+        self.read_byte();
+        self.stream.write_all(b"+").unwrap();
+        String::from_utf8(reply).unwrap()
+    }
+}
+
+fn le_hex(v: u64) -> String {
+    v.to_le_bytes()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn hex(s: &str) -> String {
+    s.bytes().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Outputs and final memory of the served program.
+type Served = thread::JoinHandle<(Vec<i64>, Vec<i64>)>;
+
+/// Serve `program` in a thread, returning a connected client.
+fn start(program: Vec<i64>) -> (Client, Served) {
+    start_with(Intcode::new(&program))
+}
+
+fn start_with(intcode: Intcode) -> (Client, Served) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let handle = thread::spawn(move || {
+        let mut server = Server::new(intcode, &[]);
+        server.serve(&listener).unwrap();
+        (server.outputs().to_vec(), server.intcode().memory.clone())
+    });
+
+    let stream = TcpStream::connect(addr).unwrap();
+    stream.set_nodelay(true).unwrap();
+    (Client { stream }, handle)
+}
+
+// read an input, output it doubled, then halt
+const PROGRAM: [i64; 10] = [3, 9, 1002, 9, 2, 9, 4, 9, 99, 0];
+
+#[test]
+fn session() {
+    let (mut client, handle) = start(PROGRAM.to_vec());
+
+    assert!(client
+        .send("qSupported:xmlRegisters=i386")
+        .contains("qXfer:features:read+"));
+    assert_eq!(client.send("?"), "S05");
+    let xml = client.send("qXfer:features:read:target.xml:0,fff");
+    assert!(xml.starts_with('l') && xml.contains("rel_base"));
+
+    // registers
+    assert_eq!(client.send("g"), format!("{}{}", le_hex(0), le_hex(0)));
+    assert_eq!(client.send("p1"), le_hex(0));
+    assert_eq!(client.send(&format!("P1={}", le_hex(5))), "OK");
+    assert_eq!(client.send("p1"), le_hex(5));
+    assert_eq!(client.send(&format!("P0={}", le_hex(3))), "E01");
+    assert_eq!(client.send("G0000"), "E01");
+    assert_eq!(
+        client.send(&format!("G{}\u{e9}{}", &le_hex(0)[..15], &le_hex(5)[1..])),
+        "E01"
+    );
+
+    // memory: cell 2 is 1002
+    assert_eq!(client.send("m10,8"), le_hex(1002));
+    assert_eq!(client.send("m11,2"), "0300");
+    assert_eq!(client.send("m1000,8"), le_hex(0));
+
+    // no input: the program stops right away
+    assert_eq!(client.send("s"), "S11");
+    assert_eq!(client.send(&format!("qRcmd,{}", hex("input 7,x"))), "E01");
+    assert_eq!(client.send(&format!("qRcmd,{}", hex("input 21"))), "OK");
+    assert_eq!(client.send("s"), "S05");
+    assert_eq!(client.send("p0"), le_hex(2 * 8));
+    assert_eq!(client.send("m48,8"), le_hex(21));
+
+    // patch the multiplier to 3, and break on the output
+    assert_eq!(client.send(&format!("M20,8:{}", le_hex(3))), "OK");
+    assert_eq!(client.send("Z0,30,1"), "OK");
+    assert_eq!(client.send("c"), "S05");
+    assert_eq!(client.send("p0"), le_hex(6 * 8));
+    assert_eq!(client.send("z0,30,1"), "OK");
+
+    assert_eq!(client.send("c"), "W00");
+    assert_eq!(
+        client.send(&format!("qRcmd,{}", hex("outputs"))),
+        hex("63\n")
+    );
+    assert_eq!(client.send("vMustReplyEmpty"), "");
+    assert_eq!(client.send("D"), "OK");
+
+    let (outputs, memory) = handle.join().unwrap();
+    assert_eq!(outputs, [63]);
+    assert_eq!(memory[9], 63);
+}
+
+#[test]
+fn errors_and_interrupts() {
+    // loop forever
+    let (mut client, handle) = start(vec![1105, 1, 0]);
+
+    client.stream.write_all(b"$c#63").unwrap();
+    assert_eq!(client.read_byte(), b'+');
+    client.stream.write_all(&[0x03]).unwrap();
+    assert_eq!(client.receive(), "S02");
+
+    // jump to an invalid opcode
+    assert_eq!(client.send(&format!("M0,8:{}", le_hex(42))), "OK");
+    assert_eq!(client.send(&format!("P0={}", le_hex(0))), "OK");
+    assert_eq!(client.send("s"), "S04");
+
+    // bad checksum is nacked
+    client.stream.write_all(b"$g#00").unwrap();
+    assert_eq!(client.read_byte(), b'-');
+
+    client.stream.write_all(b"$k#6b").unwrap();
+    assert_eq!(client.read_byte(), b'+');
+    handle.join().unwrap();
+}
+
+#[test]
+fn memory_limit() {
+    let mut intcode = Intcode::new(&[99]);
+    intcode.set_limits(Limits {
+        max_memory: Some(4),
+        ..Limits::default()
+    });
+    let (mut client, handle) = start_with(intcode);
+
+    assert_eq!(client.send(&format!("M18,8:{}", le_hex(7))), "OK");
+    assert_eq!(client.send(&format!("M20,8:{}", le_hex(7))), "E01");
+    assert_eq!(client.send("M1c,8:0000000000000000"), "E01");
+    assert_eq!(client.send("D"), "OK");
+
+    let (_, memory) = handle.join().unwrap();
+    assert_eq!(memory, [99, 0, 0, 7]);
+}
@@ -0,0 +1,71 @@
+use intcode::{Error, HostCalls, Intcode};
+use std::sync::{Arc, Mutex};
+
+const HCALL: i64 = 80;
+
+fn host_calls(log: Arc<Mutex<Vec<i64>>>) -> HostCalls {
+    let mut calls = HostCalls::new();
+
+    // 1: divmod(a, b) -> (a / b, a % b)
+    calls.register(1, 2, 2, |args, results| {
+        if args[1] == 0 {
+            return Err(Error::Custom("division by zero".to_owned()));
+        }
+        results[0] = args[0] / args[1];
+        results[1] = args[0] % args[1];
+        Ok(())
+    });
+    // 2: log(v)
+    calls.register(2, 1, 0, move |args, _| {
+        log.lock().unwrap().push(args[0]);
+        Ok(())
+    });
+    calls
+}
+
+fn new_intcode(memory: &[i64]) -> (Intcode, Arc<Mutex<Vec<i64>>>) {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let mut intcode = Intcode::new(memory);
+    intcode
+        .enable_host_calls(HCALL, host_calls(log.clone()))
+        .unwrap();
+    (intcode, log)
+}
+
+#[test]
+fn call() {
+    // divmod(17, 5) into 12..14, then output both
+    let (mut intcode, _) = new_intcode(&[180, 1, 10, 12, 4, 12, 4, 13, 99, 0, 17, 5, 0, 0]);
+
+    assert_eq!(intcode.run(&[]), [3, 2]);
+}
+
+#[test]
+fn relative_args() {
+    // read input on the stack, log it, twice
+    let (mut intcode, log) =
+        new_intcode(&[109, 20, 203, 0, 22180, 2, 0, 0, 203, 1, 22180, 2, 1, 0, 99]);
+
+    assert_eq!(intcode.run(&[7, 8]), []);
+    assert!(intcode.is_done());
+    assert_eq!(*log.lock().unwrap(), [7, 8]);
+}
+
+#[test]
+fn errors() {
+    let (mut intcode, _) = new_intcode(&[180, 3, 0, 0, 99]);
+    assert_eq!(intcode.try_run(&[]), Err(Error::UnknownHostCall(3)));
+
+    let (mut intcode, _) = new_intcode(&[180, 1, 5, 5, 99, 0]);
+    assert_eq!(
+        intcode.try_run(&[]),
+        Err(Error::Custom("division by zero".to_owned()))
+    );
+
+    let mut intcode = Intcode::new(&[180, 2, 0, 0, 99]);
+    assert_eq!(intcode.try_run(&[]), Err(Error::UnknownOpcode(HCALL)));
+    assert_eq!(
+        intcode.enable_host_calls(9, HostCalls::new()),
+        Err(Error::ReservedOpcode(9))
+    );
+}
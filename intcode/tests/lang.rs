@@ -0,0 +1,326 @@
+use intcode::lang::{compile, CompileError, Pos};
+use intcode::{Error, Intcode};
+use proptest::prelude::*;
+
+fn run(source: &str, inputs: &[i64]) -> Vec<i64> {
+    let program = compile(source).unwrap();
+    let mut intcode = Intcode::new(&program);
+    let output = intcode.run(inputs);
+    assert!(intcode.is_done());
+    output
+}
+
+fn error(source: &str) -> (usize, usize, String) {
+    let CompileError { pos, message } = compile(source).unwrap_err();
+    (pos.line, pos.column, message)
+}
+
+#[test]
+fn arithmetic() {
+    let source = "
+        fn main() {
+            output(1 + 2 * 3);
+            output((1 + 2) * 3);
+            output(10 - 4 - 3);
+            output(-5 * -(2 - 4));
+            let x = input();
+            output(x - 1);
+            output(2 - x);
+        }
+    ";
+    assert_eq!(run(source, &[7]), vec![7, 9, 3, -10, 6, -5]);
+}
+
+#[test]
+fn comparisons() {
+    let source = "
+        fn main() {
+            let a = input();
+            let b = input();
+            output(a < b);
+            output(a <= b);
+            output(a > b);
+            output(a >= b);
+            output(a == b);
+            output(a != b);
+            output(!a);
+        }
+    ";
+    assert_eq!(run(source, &[1, 2]), vec![1, 1, 0, 0, 0, 1, 0]);
+    assert_eq!(run(source, &[2, 2]), vec![0, 1, 0, 1, 1, 0, 0]);
+    assert_eq!(run(source, &[0, -1]), vec![0, 0, 1, 1, 0, 1, 1]);
+}
+
+#[test]
+fn short_circuit() {
+    let source = "
+        fn side(v) {
+            output(v);
+            return v;
+        }
+
+        fn main() {
+            output(side(0) && side(1));
+            output(side(2) && side(3));
+            output(side(4) || side(5));
+            output(side(0) || side(0));
+        }
+    ";
+    assert_eq!(run(source, &[]), vec![0, 0, 2, 3, 1, 4, 1, 0, 0, 0]);
+}
+
+#[test]
+fn control_flow() {
+    let source = "
+        fn sign(x) {
+            if x < 0 {
+                return -1;
+            } else if x == 0 {
+                return 0;
+            } else {
+                return 1;
+            }
+        }
+
+        fn main() {
+            let n = input();
+            let i = 0;
+            while i < n {
+                let x = input();
+                output(sign(x));
+                i = i + 1;
+            }
+        }
+    ";
+    assert_eq!(run(source, &[4, -3, 0, 8, -1]), vec![-1, 0, 1, -1]);
+}
+
+#[test]
+fn scopes() {
+    let source = "
+        fn main() {
+            let x = 1;
+            if 1 {
+                let x = x + 10;
+                output(x);
+            }
+            output(x);
+        }
+    ";
+    assert_eq!(run(source, &[]), vec![11, 1]);
+}
+
+#[test]
+fn recursion() {
+    let source = "
+        fn fact(n) {
+            if n < 2 {
+                return 1;
+            }
+            return n * fact(n - 1);
+        }
+
+        fn fib(n) {
+            if n < 2 {
+                return n;
+            }
+            return fib(n - 1) + fib(n - 2);
+        }
+
+        fn main() {
+            output(fact(input()));
+            output(fib(input()));
+        }
+    ";
+    assert_eq!(run(source, &[10, 20]), vec![3628800, 6765]);
+}
+
+#[test]
+fn arguments() {
+    let source = "
+        fn sub(a, b) {
+            return a - b;
+        }
+
+        fn nothing() {
+        }
+
+        fn main() {
+            output(sub(10, 3));
+            output(sub(sub(10, 3), sub(2, 5)));
+            output(nothing());
+        }
+    ";
+    assert_eq!(run(source, &[]), vec![7, 10, 0]);
+}
+
+#[test]
+fn waits_for_input() {
+    let source = "
+        // doubles every input
+        fn main() {
+            while 1 {
+                output(input() * 2);
+            }
+        }
+    ";
+    let mut intcode = Intcode::new(&compile(source).unwrap());
+    assert_eq!(intcode.run(&[1, 2]), vec![2, 4]);
+    assert_eq!(intcode.run(&[]), vec![]);
+    assert_eq!(intcode.run(&[-5]), vec![-10]);
+    assert!(!intcode.is_done());
+}
+
+#[test]
+fn runtime_errors() {
+    let source = "
+        fn main() {
+            output(input() * 4611686018427387904);
+        }
+    ";
+    let mut intcode = Intcode::new(&compile(source).unwrap());
+    assert_eq!(intcode.try_run(&[2]), Err(Error::Overflow));
+}
+
+#[test]
+fn compile_errors() {
+    assert_eq!(error("fn f() {}"), (1, 1, "no `main` function".to_owned()));
+    assert_eq!(
+        error("fn main() {\n    x = 1;\n}"),
+        (2, 5, "unknown variable `x`".to_owned())
+    );
+    assert_eq!(
+        error("fn main() {\n    output(y);\n}"),
+        (2, 12, "unknown variable `y`".to_owned())
+    );
+    assert_eq!(
+        error("fn main() {\n    f(1);\n}"),
+        (2, 5, "unknown function `f`".to_owned())
+    );
+    assert_eq!(
+        error("fn f(a) {}\nfn main() { f(); }"),
+        (2, 13, "`f` takes 1 argument(s), 0 given".to_owned())
+    );
+    assert_eq!(
+        error("fn main() {}\nfn main() {}"),
+        (2, 1, "function `main` is already defined".to_owned())
+    );
+    assert_eq!(
+        error("fn input() {}"),
+        (1, 1, "function `input` is already defined".to_owned())
+    );
+    assert_eq!(
+        error("fn main() {}\nfn f(a, b, a) { return a; }"),
+        (2, 1, "parameter `a` is already defined".to_owned())
+    );
+    assert_eq!(
+        error("fn main() {\n    let x = 1 +;\n}"),
+        (2, 16, "expected an expression, found `;`".to_owned())
+    );
+    assert_eq!(
+        error("fn main() {\n    output(1)\n}"),
+        (3, 1, "expected `;`, found `}`".to_owned())
+    );
+    assert_eq!(
+        error("fn main() { output(1 / 2); }"),
+        (1, 22, "unexpected character `/`".to_owned())
+    );
+    assert_eq!(
+        error("fn main() {"),
+        (2, 1, "expected an expression, found end of file".to_owned())
+    );
+    assert_eq!(
+        error("fn main() { 99999999999999999999; }"),
+        (1, 13, "integer `99999999999999999999` too large".to_owned())
+    );
+    assert_eq!(
+        compile("let x = 1;").unwrap_err().to_string(),
+        "1:1: expected `fn`, found `let`"
+    );
+    assert_eq!(
+        compile("fn main(a) {}").unwrap_err().pos,
+        Pos { line: 1, column: 1 }
+    );
+}
+
+// {{{ Random expressions
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Int(i64),
+    Var(usize),
+    Neg(Box<Expr>),
+    Not(Box<Expr>),
+    Bin(&'static str, Box<Expr>, Box<Expr>),
+}
+
+const OPS: [&str; 11] = ["+", "-", "*", "<", "<=", ">", ">=", "==", "!=", "&&", "||"];
+
+impl Expr {
+    fn source(&self) -> String {
+        match self {
+            Self::Int(v) => v.to_string(),
+            Self::Var(idx) => format!("v{}", idx),
+            Self::Neg(e) => format!("-({})", e.source()),
+            Self::Not(e) => format!("!({})", e.source()),
+            Self::Bin(op, a, b) => format!("({} {} {})", a.source(), op, b.source()),
+        }
+    }
+
+    fn eval(&self, vars: &[i64]) -> i64 {
+        match self {
+            Self::Int(v) => *v,
+            Self::Var(idx) => vars[*idx],
+            Self::Neg(e) => -e.eval(vars),
+            Self::Not(e) => (e.eval(vars) == 0) as i64,
+            Self::Bin(op, a, b) => {
+                let a = a.eval(vars);
+                let b = b.eval(vars);
+                match *op {
+                    "+" => a + b,
+                    "-" => a - b,
+                    "*" => a * b,
+                    "<" => (a < b) as i64,
+                    "<=" => (a <= b) as i64,
+                    ">" => (a > b) as i64,
+                    ">=" => (a >= b) as i64,
+                    "==" => (a == b) as i64,
+                    "!=" => (a != b) as i64,
+                    "&&" => (a != 0 && b != 0) as i64,
+                    _ => (a != 0 || b != 0) as i64,
+                }
+            }
+        }
+    }
+}
+
+fn expr() -> impl Strategy<Value = Expr> {
+    let leaf = prop_oneof![
+        (0i64..10).prop_map(Expr::Int),
+        (0usize..3).prop_map(Expr::Var)
+    ];
+    leaf.prop_recursive(4, 16, 2, |inner| {
+        prop_oneof![
+            inner.clone().prop_map(|e| Expr::Neg(Box::new(e))),
+            inner.clone().prop_map(|e| Expr::Not(Box::new(e))),
+            (0..OPS.len(), inner.clone(), inner).prop_map(|(op, a, b)| Expr::Bin(
+                OPS[op],
+                Box::new(a),
+                Box::new(b)
+            )),
+        ]
+    })
+}
+
+proptest! {
+    #[test]
+    fn expressions(e in expr(), vars in prop::collection::vec(-20i64..20, 3)) {
+        let source = format!(
+            "fn main() {{ let v0 = input(); let v1 = input(); let v2 = input(); output({}); }}",
+            e.source()
+        );
+        prop_assert_eq!(run(&source, &vars), vec![e.eval(&vars)]);
+    }
+}
+
+// }}}
@@ -0,0 +1,106 @@
+use intcode::{Error, Intcode, Limits};
+use std::time::{Duration, Instant};
+
+// output the counter, then increment it, forever
+const COUNTER: [i64; 8] = [4, 7, 1001, 7, 1, 7, 1105, 1];
+
+fn counter() -> Intcode {
+    let mut memory = COUNTER.to_vec();
+    memory.extend(&[0, 0]);
+    memory[7] = 0;
+    Intcode::new(&memory)
+}
+
+#[test]
+fn instructions() {
+    let mut intcode = Intcode::new(&[1101, 1, 1, 0, 1101, 1, 1, 0, 99]);
+    intcode.set_limits(Limits {
+        max_instructions: Some(2),
+        ..Limits::default()
+    });
+    assert_eq!(intcode.try_run(&[]), Err(Error::InstructionLimit));
+    assert_eq!(intcode.nb_instructions(), 2);
+
+    // raise the limit and resume
+    intcode.set_limits(Limits::default());
+    assert_eq!(intcode.try_run(&[]), Ok(vec![]));
+    assert!(intcode.is_done());
+    assert_eq!(intcode.nb_instructions(), 3);
+}
+
+#[test]
+fn waiting_for_input_is_not_counted() {
+    let mut intcode = Intcode::new(&[3, 0, 99]);
+
+    intcode.run(&[]);
+    intcode.run(&[]);
+    assert_eq!(intcode.nb_instructions(), 0);
+    intcode.run(&[1]);
+    assert_eq!(intcode.nb_instructions(), 2);
+}
+
+#[test]
+fn memory() {
+    let mut intcode = Intcode::new(&[1101, 1, 1, 100, 1101, 1, 1, 1000, 99]);
+    intcode.set_limits(Limits {
+        max_memory: Some(1000),
+        ..Limits::default()
+    });
+
+    assert_eq!(intcode.try_run(&[]), Err(Error::MemoryLimit(1000)));
+    assert_eq!(intcode.memory.len(), 101);
+}
+
+#[test]
+fn outputs() {
+    let mut intcode = counter();
+    intcode.set_limits(Limits {
+        max_outputs: Some(3),
+        ..Limits::default()
+    });
+    assert_eq!(intcode.try_run(&[]), Err(Error::OutputLimit));
+
+    intcode.set_limits(Limits {
+        max_outputs: Some(5),
+        ..Limits::default()
+    });
+    assert_eq!(intcode.try_run(&[]), Err(Error::OutputLimit));
+
+    // the run is interrupted right before the 6th output
+    assert_eq!(intcode.memory[7], 5);
+}
+
+#[test]
+fn partial_outputs() {
+    let mut intcode = counter();
+    intcode.set_limits(Limits {
+        max_outputs: Some(3),
+        ..Limits::default()
+    });
+    assert_eq!(intcode.try_run(&[]), Err(Error::OutputLimit));
+    assert_eq!(intcode.take_pending_outputs(), [0, 1, 2]);
+    assert_eq!(intcode.take_pending_outputs(), []);
+
+    // when not taken, they are returned by the resumed run
+    let mut intcode = Intcode::new(&[104, 1, 104, 2, 104, 3, 99]);
+    intcode.set_limits(Limits {
+        max_outputs: Some(2),
+        ..Limits::default()
+    });
+    assert_eq!(intcode.try_run(&[]), Err(Error::OutputLimit));
+    intcode.set_limits(Limits::default());
+    assert_eq!(intcode.try_run(&[]), Ok(vec![1, 2, 3]));
+}
+
+#[test]
+fn deadline() {
+    let mut intcode = counter();
+    let start = Instant::now();
+    intcode.set_limits(Limits {
+        deadline: Some(start + Duration::from_millis(50)),
+        ..Limits::default()
+    });
+
+    assert_eq!(intcode.try_run(&[]), Err(Error::Deadline));
+    assert!(start.elapsed() >= Duration::from_millis(50));
+}
@@ -0,0 +1,109 @@
+use intcode::{Change, Error, Intcode, Param};
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn read_write() {
+    let mut intcode = Intcode::new(&[1, 2, 3, 4, 5]);
+
+    assert_eq!(intcode.read(1..3), Ok(&[2, 3][..]));
+    assert_eq!(intcode.read(5..5), Ok(&[][..]));
+    assert_eq!(intcode.read(3..6), Err(Error::OutOfBounds(5)));
+    assert_eq!(intcode.read(7..9), Err(Error::OutOfBounds(7)));
+
+    assert_eq!(intcode.write(3, &[40, 50]), Ok(()));
+    assert_eq!(intcode.memory, vec![1, 2, 3, 40, 50]);
+    assert_eq!(intcode.write(4, &[0, 0]), Err(Error::OutOfBounds(5)));
+    assert_eq!(
+        intcode.write(usize::MAX, &[0]),
+        Err(Error::OutOfBounds(usize::MAX))
+    );
+    assert_eq!(intcode.memory, vec![1, 2, 3, 40, 50]);
+}
+
+#[test]
+fn snapshot_diff() {
+    // day 2 example
+    let mut intcode = Intcode::new(&[1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50]);
+    let before = intcode.snapshot();
+
+    intcode.run(&[]);
+    let after = intcode.snapshot();
+
+    let change = |addr, old, new| Change { addr, old, new };
+    assert_eq!(
+        before.diff(&after),
+        vec![change(0, 1, 3500), change(3, 3, 70)]
+    );
+    assert_eq!(
+        after.diff(&before),
+        vec![change(0, 3500, 1), change(3, 70, 3)]
+    );
+    assert_eq!(after.diff(&after), vec![]);
+
+    let mut intcode = Intcode::new(&[1101, 3, 4, 20, 99]);
+    let before = intcode.snapshot();
+    intcode.run(&[]);
+    assert_eq!(before.diff(&intcode.snapshot()), vec![change(20, 0, 7)]);
+    assert_eq!(intcode.snapshot().memory().len(), 21);
+}
+
+#[test]
+fn watches() {
+    let program = [3, 50, 1, 50, 100, 100, 1105, 1, 0];
+    let mut intcode = Intcode::new(&program);
+
+    let sums = Arc::new(Mutex::new(Vec::new()));
+    let s = sums.clone();
+    intcode.watch(100, move |change| s.lock().unwrap().push(*change));
+    let inputs = Arc::new(Mutex::new(0));
+    let i = inputs.clone();
+    intcode.watch(50, move |_| *i.lock().unwrap() += 1);
+
+    intcode.run(&[5, 0, 7]);
+    assert_eq!(
+        *sums.lock().unwrap(),
+        vec![
+            Change {
+                addr: 100,
+                old: 0,
+                new: 5
+            },
+            Change {
+                addr: 100,
+                old: 5,
+                new: 5
+            },
+            Change {
+                addr: 100,
+                old: 5,
+                new: 12
+            },
+        ]
+    );
+    assert_eq!(*inputs.lock().unwrap(), 3);
+
+    // writes through the API are not reported
+    intcode.write(100, &[0]).unwrap();
+    intcode.unwatch(50);
+    intcode.run(&[1]);
+    assert_eq!(sums.lock().unwrap().len(), 4);
+    assert_eq!(sums.lock().unwrap()[3].old, 0);
+    assert_eq!(*inputs.lock().unwrap(), 3);
+}
+
+#[test]
+fn watches_custom_opcodes() {
+    let mut intcode = Intcode::new(&[42, 10, 99]);
+    intcode
+        .register_opcode(42, &[Param::Out], |args| {
+            args[0] = 7;
+            Ok(())
+        })
+        .unwrap();
+
+    let seen = Arc::new(Mutex::new(None));
+    let s = seen.clone();
+    intcode.watch(10, move |change| *s.lock().unwrap() = Some(change.new));
+    intcode.run(&[]);
+    assert_eq!(*seen.lock().unwrap(), Some(7));
+}
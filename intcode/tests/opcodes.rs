@@ -0,0 +1,120 @@
+use intcode::{Error, Intcode, Param};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Register division (10), modulo (11) and bitwise and (12).
+fn extended(memory: &[i64]) -> Intcode {
+    let mut intcode = Intcode::new(memory);
+    let binop = [Param::In, Param::In, Param::Out];
+
+    intcode
+        .register_opcode(10, &binop, |args| {
+            args[2] = args[0]
+                .checked_div(args[1])
+                .ok_or_else(|| Error::Custom("division by zero".to_owned()))?;
+            Ok(())
+        })
+        .unwrap();
+    intcode
+        .register_opcode(11, &binop, |args| {
+            args[2] = args[0]
+                .checked_rem(args[1])
+                .ok_or_else(|| Error::Custom("division by zero".to_owned()))?;
+            Ok(())
+        })
+        .unwrap();
+    intcode
+        .register_opcode(12, &binop, |args| {
+            args[2] = args[0] & args[1];
+            Ok(())
+        })
+        .unwrap();
+    intcode
+}
+
+#[test]
+fn arithmetic() {
+    // 17 / 5, 17 % 5, 12 & 10
+    let mut intcode = extended(&[
+        1110, 17, 5, 20, 1111, 17, 5, 21, 1112, 12, 10, 22, 4, 20, 4, 21, 4, 22, 99,
+    ]);
+
+    assert_eq!(intcode.run(&[]), [3, 2, 8]);
+}
+
+#[test]
+fn modes() {
+    // position read, relative write
+    let mut intcode = extended(&[109, 10, 20010, 8, 9, 0, 99, 0, 42, 7]);
+
+    assert_eq!(intcode.run(&[]), []);
+    assert_eq!(intcode.memory[10], 6);
+
+    let mut intcode = extended(&[11110, 1, 2, 3, 99]);
+    assert_eq!(intcode.try_run(&[]), Err(Error::InvalidOutputMode(1)));
+}
+
+#[test]
+fn exec_error() {
+    let mut intcode = extended(&[1110, 1, 0, 0, 99]);
+
+    assert_eq!(
+        intcode.try_run(&[]),
+        Err(Error::Custom("division by zero".to_owned()))
+    );
+}
+
+#[test]
+fn reserved() {
+    let mut intcode = Intcode::new(&[99]);
+    let nop = |_: &mut [i64]| Ok(());
+
+    for opcode in &[1, 4, 9, 99, -1, 100] {
+        assert_eq!(
+            intcode.register_opcode(*opcode, &[], nop),
+            Err(Error::ReservedOpcode(*opcode))
+        );
+    }
+    assert_eq!(intcode.register_opcode(0, &[], nop), Ok(()));
+    assert_eq!(intcode.register_opcode(98, &[], nop), Ok(()));
+}
+
+#[test]
+fn unregistered() {
+    let mut intcode = Intcode::new(&[1110, 17, 5, 0, 99]);
+
+    assert_eq!(intcode.try_run(&[]), Err(Error::UnknownOpcode(10)));
+}
+
+#[test]
+fn cloned_with_state() {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let mut intcode = Intcode::new(&[50, 50, 3, 0, 50, 99]);
+    let c = counter.clone();
+    intcode
+        .register_opcode(50, &[], move |_| {
+            c.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        })
+        .unwrap();
+
+    assert_eq!(intcode.run(&[]), []);
+    let mut clone = intcode.clone();
+    clone.run(&[1]);
+    intcode.run(&[1]);
+    assert_eq!(counter.load(Ordering::SeqCst), 4);
+}
+
+#[test]
+fn sent_to_thread() {
+    let mut intcode = Intcode::new(&[50, 0, 4, 0, 99]);
+    intcode
+        .register_opcode(50, &[Param::Out], |args| {
+            args[0] = 42;
+            Ok(())
+        })
+        .unwrap();
+
+    let handle = std::thread::spawn(move || intcode.run(&[]));
+    assert_eq!(handle.join().unwrap(), [42]);
+}
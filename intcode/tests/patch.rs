@@ -0,0 +1,120 @@
+use intcode::{Error, Intcode, Patch, Patches, Poke};
+
+const PATCHES: &str = "
+# noun and verb
+[1202]
+1: 12
+2: 2
+
+[free-play]
+0: 1 -> 2
+";
+
+#[test]
+fn parse() {
+    let patches: Patches = PATCHES.parse().unwrap();
+
+    let mut noun_verb = Patch::new("1202");
+    noun_verb.poke(1, 12);
+    noun_verb.poke(2, 2);
+    let mut free_play = Patch::new("free-play");
+    free_play.poke_expecting(0, 1, 2);
+    assert_eq!(patches.patches, vec![noun_verb, free_play]);
+    assert_eq!(patches.get("free-play").unwrap().pokes.len(), 1);
+    assert!(patches.get("nope").is_none());
+
+    assert_eq!(patches.to_string().parse::<Patches>(), Ok(patches));
+
+    let unnamed: Patches = "3: 4\n5: -1 -> 6\n".parse().unwrap();
+    assert_eq!(unnamed.patches.len(), 1);
+    assert_eq!(unnamed.patches[0].name, None);
+    assert_eq!(
+        unnamed.patches[0].pokes[1],
+        Poke {
+            addr: 5,
+            value: 6,
+            expected: Some(-1)
+        }
+    );
+    assert_eq!(unnamed.to_string(), "3: 4\n5: -1 -> 6\n");
+}
+
+#[test]
+fn parse_errors() {
+    let err = |s: &str| s.parse::<Patches>().unwrap_err();
+
+    assert_eq!(err("[a\n"), "line 1: missing `]`");
+    assert_eq!(err("[a]\n1 2\n"), "line 2: missing `:`");
+    assert!(err("x: 1").starts_with("line 1: invalid address `x`"));
+    assert!(err("1: 2 -> y").starts_with("line 1: invalid value `y`"));
+}
+
+#[test]
+fn apply_and_revert() {
+    let patches: Patches = PATCHES.parse().unwrap();
+    let mut intcode = Intcode::new(&[1, 0, 0, 0, 99]);
+
+    let applied = patches.get("1202").unwrap().apply(&mut intcode).unwrap();
+    assert_eq!(intcode.memory, vec![1, 12, 2, 0, 99]);
+    let free_play = patches
+        .get("free-play")
+        .unwrap()
+        .apply(&mut intcode)
+        .unwrap();
+    assert_eq!(intcode.memory, vec![2, 12, 2, 0, 99]);
+
+    applied.revert(&mut intcode).unwrap();
+    assert_eq!(intcode.memory, vec![2, 0, 0, 0, 99]);
+    free_play.revert(&mut intcode).unwrap();
+    assert_eq!(intcode.memory, vec![1, 0, 0, 0, 99]);
+
+    // poking the same address twice
+    let mut patch = Patch::default();
+    patch.poke(3, 5);
+    patch.poke(3, 6);
+    let applied = patch.apply(&mut intcode).unwrap();
+    assert_eq!(intcode.memory[3], 6);
+    applied.revert(&mut intcode).unwrap();
+    assert_eq!(intcode.memory[3], 0);
+}
+
+#[test]
+fn validation() {
+    let mut intcode = Intcode::new(&[2, 0, 0, 0, 99]);
+
+    let mut patch = Patch::new("check");
+    patch.poke(1, 5);
+    patch.poke_expecting(0, 1, 2);
+    let error = Error::UnexpectedValue {
+        addr: 0,
+        expected: 1,
+        actual: 2,
+    };
+    assert_eq!(patch.validate(&intcode), Err(error.clone()));
+    assert_eq!(patch.apply(&mut intcode), Err(error));
+    assert_eq!(intcode.memory, vec![2, 0, 0, 0, 99]);
+
+    let mut patch = Patch::new("bounds");
+    patch.poke(0, 1);
+    patch.poke(5, 1);
+    assert_eq!(patch.apply(&mut intcode), Err(Error::OutOfBounds(5)));
+    assert_eq!(intcode.memory, vec![2, 0, 0, 0, 99]);
+
+    let patches: Patches = format!("{}: 1\n", usize::MAX).parse().unwrap();
+    assert_eq!(
+        patches.patches[0].apply(&mut intcode),
+        Err(Error::OutOfBounds(usize::MAX))
+    );
+}
+
+#[test]
+fn find() {
+    let intcode = Intcode::new(&[1, 2, 3, 1, 2, 4, 1, 2]);
+
+    assert_eq!(intcode.find(&[Some(1), Some(2)]), vec![0, 3, 6]);
+    assert_eq!(intcode.find(&[Some(1), Some(2), None]), vec![0, 3]);
+    assert_eq!(intcode.find(&[Some(2), None, Some(1)]), vec![1, 4]);
+    assert_eq!(intcode.find(&[Some(5)]), vec![]);
+    assert_eq!(intcode.find(&[]), vec![]);
+    assert_eq!(intcode.find(&[None; 9]), vec![]);
+}
@@ -0,0 +1,108 @@
+use intcode::{Divergence, Error, Exchange, Intcode, Recorder, Transcript};
+
+// output twice each input, until 0 is read
+fn doubler() -> Intcode {
+    Intcode::new(&[
+        3, 100, 1006, 100, 14, 1002, 100, 2, 101, 4, 101, 1105, 1, 0, 99,
+    ])
+}
+
+/// Drive the doubler with a controller choosing the next input from
+/// the last output.
+fn session(intcode: Intcode) -> Recorder {
+    let mut recorder = Recorder::new(intcode);
+    let mut input = 1;
+
+    while !recorder.is_done() {
+        let outputs = recorder.run(&[input]);
+        input = match outputs.last() {
+            Some(v) if *v < 20 => *v + 1,
+            _ => 0,
+        };
+    }
+    recorder
+}
+
+#[test]
+fn record_and_replay() {
+    let transcript = session(doubler()).into_transcript();
+
+    assert_eq!(transcript.exchanges.len(), 5);
+    assert_eq!(
+        transcript.exchanges[1],
+        Exchange {
+            inputs: vec![3],
+            outputs: vec![6]
+        }
+    );
+    assert!(transcript.halted);
+    assert_eq!(transcript.replay(doubler()), Ok(()));
+}
+
+#[test]
+fn save_and_load() {
+    let transcript = session(doubler()).into_transcript();
+    let path = std::env::temp_dir().join(format!("intcode-record-{}.txt", std::process::id()));
+
+    transcript.save(&path).unwrap();
+    let loaded = Transcript::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded, transcript);
+    assert_eq!(
+        transcript.to_string(),
+        "1 -> 2\n3 -> 6\n7 -> 14\n15 -> 30\n0 -> \nhalted\n"
+    );
+}
+
+#[test]
+fn divergences() {
+    let mut transcript: Transcript = "1 -> 2\n3 -> 7\n".parse().unwrap();
+    assert_eq!(
+        transcript.replay(doubler()),
+        Err(Divergence::Output {
+            exchange: 1,
+            index: 0,
+            expected: Some(7),
+            actual: Some(6),
+        })
+    );
+
+    transcript.exchanges[1].outputs = vec![6, 6];
+    assert_eq!(
+        transcript.replay(doubler()),
+        Err(Divergence::Output {
+            exchange: 1,
+            index: 1,
+            expected: Some(6),
+            actual: None,
+        })
+    );
+
+    transcript.exchanges[1].outputs = vec![6];
+    transcript.halted = true;
+    assert_eq!(
+        transcript.replay(doubler()),
+        Err(Divergence::Halted {
+            expected: true,
+            actual: false,
+        })
+    );
+
+    let transcript: Transcript = "1 -> 2".parse().unwrap();
+    assert_eq!(
+        transcript.replay(Intcode::new(&[3, 0, 42])),
+        Err(Divergence::Error {
+            exchange: 0,
+            error: Error::UnknownOpcode(42),
+        })
+    );
+}
+
+#[test]
+fn parse_errors() {
+    assert!("1, 2".parse::<Transcript>().is_err());
+    assert!("a -> 2".parse::<Transcript>().is_err());
+    assert!("halted\n1 -> 2".parse::<Transcript>().is_err());
+    assert_eq!(" -> \n".parse::<Transcript>().unwrap().exchanges.len(), 1);
+}
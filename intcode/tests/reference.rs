@@ -0,0 +1,338 @@
+//! Differential tests: random well-formed programs are run both on
+//! `Intcode` and on a straightforward reference interpreter, and the
+//! outputs, final state and errors are compared.
+
+use intcode::{Error, Intcode};
+use proptest::prelude::*;
+use std::collections::HashMap;
+
+// {{{ Reference interpreter
+
+const MAX_STEPS: usize = 10_000;
+const MAX_ADDRESS: usize = 1 << 16;
+
+/// Deliberately naive interpreter: sparse memory, every instruction
+/// decoded digit by digit.
+struct Reference {
+    memory: HashMap<usize, i64>,
+    len: usize,
+    eip: usize,
+    rel_base: i64,
+    is_done: bool,
+    steps: usize,
+}
+
+enum Outcome {
+    Ok(Vec<i64>),
+    Err(Error),
+    /// The program did not stop in a reasonable amount of time or
+    /// memory, and is not interesting to compare.
+    Diverged,
+}
+
+impl Reference {
+    fn new(program: &[i64]) -> Self {
+        Self {
+            memory: program.iter().cloned().enumerate().collect(),
+            len: program.len(),
+            eip: 0,
+            rel_base: 0,
+            is_done: false,
+            steps: 0,
+        }
+    }
+
+    fn read(&mut self, addr: usize) -> i64 {
+        self.len = std::cmp::max(self.len, addr + 1);
+        *self.memory.get(&addr).unwrap_or(&0)
+    }
+
+    fn write(&mut self, addr: usize, v: i64) {
+        self.len = std::cmp::max(self.len, addr + 1);
+        self.memory.insert(addr, v);
+    }
+
+    fn dense_memory(&self) -> Vec<i64> {
+        (0..self.len)
+            .map(|addr| *self.memory.get(&addr).unwrap_or(&0))
+            .collect()
+    }
+
+    fn address(&self, instruction: i64, param: u32, raw: i64) -> Result<usize, Error> {
+        let mode = (instruction / 10i64.pow(param + 2)) % 10;
+        let addr = match mode {
+            0 => raw,
+            2 => raw.checked_add(self.rel_base).ok_or(Error::Overflow)?,
+            _ => return Err(Error::InvalidOutputMode(mode)),
+        };
+        if addr < 0 {
+            Err(Error::InvalidAddress(addr))
+        } else {
+            Ok(addr as usize)
+        }
+    }
+
+    fn param(&mut self, instruction: i64, param: u32) -> Result<i64, Error> {
+        let raw = self.read(self.eip + 1 + param as usize);
+        let mode = (instruction / 10i64.pow(param + 2)) % 10;
+        match mode {
+            1 => Ok(raw),
+            0 | 2 => {
+                let addr = self.address(instruction, param, raw)?;
+                Ok(self.read(addr))
+            }
+            _ => Err(Error::InvalidMode(mode)),
+        }
+    }
+
+    fn store(&mut self, instruction: i64, param: u32, v: i64) -> Result<(), Error> {
+        let raw = self.read(self.eip + 1 + param as usize);
+        let addr = self.address(instruction, param, raw)?;
+        self.write(addr, v);
+        Ok(())
+    }
+
+    fn jump_target(v: i64) -> Result<usize, Error> {
+        if v < 0 {
+            Err(Error::InvalidAddress(v))
+        } else {
+            Ok(v as usize)
+        }
+    }
+
+    fn step(&mut self, inputs: &mut &[i64], outputs: &mut Vec<i64>) -> Result<bool, Error> {
+        let instruction = self.read(self.eip);
+
+        match instruction % 100 {
+            op @ 1 | op @ 2 | op @ 7 | op @ 8 => {
+                let a = self.param(instruction, 0)?;
+                let b = self.param(instruction, 1)?;
+                let v = match op {
+                    1 => a.checked_add(b).ok_or(Error::Overflow)?,
+                    2 => a.checked_mul(b).ok_or(Error::Overflow)?,
+                    7 => (a < b) as i64,
+                    _ => (a == b) as i64,
+                };
+                self.store(instruction, 2, v)?;
+                self.eip += 4;
+            }
+            3 => match inputs.split_first() {
+                Some((v, rest)) => {
+                    self.store(instruction, 0, *v)?;
+                    *inputs = rest;
+                    self.eip += 2;
+                }
+                None => return Ok(false),
+            },
+            4 => {
+                let v = self.param(instruction, 0)?;
+                outputs.push(v);
+                self.eip += 2;
+            }
+            op @ 5 | op @ 6 => {
+                let v = self.param(instruction, 0)?;
+                if (v != 0) == (op == 5) {
+                    let target = self.param(instruction, 1)?;
+                    self.eip = Self::jump_target(target)?;
+                } else {
+                    self.eip += 3;
+                }
+            }
+            9 => {
+                let v = self.param(instruction, 0)?;
+                self.rel_base = self.rel_base.checked_add(v).ok_or(Error::Overflow)?;
+                self.eip += 2;
+            }
+            99 => {
+                self.is_done = true;
+                return Ok(false);
+            }
+            op => return Err(Error::UnknownOpcode(op)),
+        }
+        Ok(true)
+    }
+
+    fn run(&mut self, mut inputs: &[i64]) -> Outcome {
+        let mut outputs = Vec::new();
+
+        loop {
+            if self.steps >= MAX_STEPS || self.eip > MAX_ADDRESS || self.len > MAX_ADDRESS {
+                return Outcome::Diverged;
+            }
+            self.steps += 1;
+            let res = self.step(&mut inputs, &mut outputs);
+            if self.len > MAX_ADDRESS {
+                // refuse to compare huge allocations on the VM side
+                return Outcome::Diverged;
+            }
+            match res {
+                Ok(true) => (),
+                Ok(false) => return Outcome::Ok(outputs),
+                Err(e) => return Outcome::Err(e),
+            }
+        }
+    }
+}
+
+// }}}
+// {{{ Program generation
+
+/// Number of scratch cells placed right after the code.
+const DATA_LEN: i64 = 16;
+
+#[derive(Debug, Clone)]
+enum Param {
+    /// Position mode, index in the data region.
+    Data(i64),
+    /// Position mode, raw address (may point into the code).
+    Position(i64),
+    Immediate(i64),
+    Relative(i64),
+    /// Immediate mode, address of the n-th instruction.
+    Label(usize),
+}
+
+#[derive(Debug, Clone)]
+struct Instr {
+    opcode: i64,
+    params: Vec<Param>,
+}
+
+fn read_param() -> impl Strategy<Value = Param> {
+    prop_oneof![
+        4 => (0..DATA_LEN).prop_map(Param::Data),
+        1 => (0i64..64).prop_map(Param::Position),
+        3 => (-20i64..20).prop_map(Param::Immediate),
+        2 => (-4i64..DATA_LEN).prop_map(Param::Relative),
+    ]
+}
+
+fn write_param() -> impl Strategy<Value = Param> {
+    prop_oneof![
+        4 => (0..DATA_LEN).prop_map(Param::Data),
+        1 => (0i64..64).prop_map(Param::Position),
+        2 => (-4i64..DATA_LEN).prop_map(Param::Relative),
+    ]
+}
+
+fn jump_param() -> impl Strategy<Value = Param> {
+    prop_oneof![
+        4 => (0usize..64).prop_map(Param::Label),
+        1 => read_param(),
+    ]
+}
+
+fn instr() -> impl Strategy<Value = Instr> {
+    let instr = |opcode, params: Vec<_>| Instr { opcode, params };
+
+    prop_oneof![
+        (1i64..3, read_param(), read_param(), write_param())
+            .prop_map(move |(op, a, b, c)| instr(op, vec![a, b, c])),
+        (7i64..9, read_param(), read_param(), write_param())
+            .prop_map(move |(op, a, b, c)| instr(op, vec![a, b, c])),
+        write_param().prop_map(move |a| instr(3, vec![a])),
+        read_param().prop_map(move |a| instr(4, vec![a])),
+        (5i64..7, read_param(), jump_param()).prop_map(move |(op, a, b)| instr(op, vec![a, b])),
+        read_param().prop_map(move |a| instr(9, vec![a])),
+        Just(instr(99, vec![])),
+    ]
+}
+
+/// Lay out the instructions in memory, followed by a halt and the
+/// data region.
+fn assemble(instrs: &[Instr], data: &[i64]) -> Vec<i64> {
+    let mut addrs = Vec::new();
+    let mut code_len = 0;
+    for instr in instrs {
+        addrs.push(code_len as i64);
+        code_len += 1 + instr.params.len();
+    }
+    addrs.push(code_len as i64);
+    code_len += 1;
+
+    let mut memory = Vec::new();
+    for instr in instrs {
+        let mut opcode = instr.opcode;
+        let mut params = Vec::new();
+        for (i, param) in instr.params.iter().enumerate() {
+            let (mode, v) = match param {
+                Param::Data(idx) => (0, code_len as i64 + idx),
+                Param::Position(v) => (0, *v),
+                Param::Immediate(v) => (1, *v),
+                Param::Relative(v) => (2, *v),
+                Param::Label(n) => (1, addrs[n % addrs.len()]),
+            };
+            opcode += mode * 10i64.pow(i as u32 + 2);
+            params.push(v);
+        }
+        memory.push(opcode);
+        memory.extend(params);
+    }
+    memory.push(99);
+    memory.extend(data);
+    memory
+}
+
+fn program() -> impl Strategy<Value = Vec<i64>> {
+    (
+        prop::collection::vec(instr(), 1..32),
+        prop::collection::vec(-50i64..50, DATA_LEN as usize),
+    )
+        .prop_map(|(instrs, data)| assemble(&instrs, &data))
+}
+
+// }}}
+// {{{ Properties
+
+/// Run both interpreters and check they agree. Returns `None` if the
+/// program diverged, otherwise whether execution can be resumed.
+fn compare(vm: &mut Intcode, reference: &mut Reference, inputs: &[i64]) -> Option<bool> {
+    let expected = match reference.run(inputs) {
+        Outcome::Diverged => return None,
+        Outcome::Ok(outputs) => Ok(outputs),
+        Outcome::Err(e) => Err(e),
+    };
+
+    let res = vm.try_run(inputs);
+    assert_eq!(res, expected);
+    if res.is_ok() {
+        assert_eq!(vm.is_done(), reference.is_done);
+        assert_eq!(vm.memory, reference.dense_memory());
+    }
+    Some(res.is_ok() && !vm.is_done())
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(2000))]
+
+    #[test]
+    fn same_as_reference(
+        program in program(),
+        inputs in prop::collection::vec(-100i64..100, 0..6),
+        resume_inputs in prop::collection::vec(-100i64..100, 0..6),
+    ) {
+        let mut vm = Intcode::new(&program);
+        let mut reference = Reference::new(&program);
+
+        let resumable = compare(&mut vm, &mut reference, &inputs);
+        prop_assume!(resumable.is_some());
+        if resumable == Some(true) {
+            // the VM stopped waiting for more inputs, resume it
+            compare(&mut vm, &mut reference, &resume_inputs);
+        }
+    }
+
+    #[test]
+    fn never_panics(
+        memory in prop::collection::vec(-2000i64..2000, 0..64),
+        inputs in prop::collection::vec(any::<i64>(), 0..8),
+    ) {
+        let mut reference = Reference::new(&memory);
+
+        // only run programs that the reference proved to terminate
+        prop_assume!(!matches!(reference.run(&inputs), Outcome::Diverged));
+        let _ = Intcode::new(&memory).try_run(&inputs);
+    }
+}
+
+// }}}
@@ -0,0 +1,163 @@
+//! Cycle detection in sequences of states.
+//!
+//! A sequence starts from an initial state, every state being computed
+//! from the previous one by an `advance` function. When there are finitely
+//! many states, the sequence ends up looping: after `start` steps, it
+//! repeats every `len` steps. The detection never returns when the
+//! sequence does not loop.
+//!
+//! [`floyd`] and [`brent`] only keep two states, while [`hashing`] keeps
+//! them all but advances the sequence the fewest times.
+use std::collections::HashMap;
+use std::hash::Hash;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// Index of the first state of the loop, also called mu.
+    pub start: u64,
+    /// Number of states in the loop, also called lambda.
+    pub len: u64,
+}
+
+impl Cycle {
+    /// The first step whose state was already seen.
+    pub fn first_repeat(&self) -> u64 {
+        self.start + self.len
+    }
+
+    /// The earliest step having the same state as step `n`.
+    pub fn reduce(&self, n: u64) -> u64 {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.len
+        }
+    }
+
+    /// The state at step `n` of the sequence, computed in at most
+    /// `start + len` steps.
+    pub fn state_at<S, F>(&self, initial: &S, mut advance: F, n: u64) -> S
+    where
+        S: Clone,
+        F: FnMut(&mut S),
+    {
+        let mut state = initial.clone();
+        for _ in 0..self.reduce(n) {
+            advance(&mut state);
+        }
+        state
+    }
+}
+
+/// Loop of a sequence made of independent sequences side by side, given
+/// their loops: it starts once they all loop, and lasts until they all
+/// line up again. None if that length does not fit in 64 bits.
+pub fn product<I>(cycles: I) -> Option<Cycle>
+where
+    I: IntoIterator<Item = Cycle>,
+{
+    cycles
+        .into_iter()
+        .try_fold(Cycle { start: 0, len: 1 }, |acc, c| {
+            Some(Cycle {
+                start: acc.start.max(c.start),
+                len: crate::lcm(acc.len, c.len)?,
+            })
+        })
+}
+
+/// Floyd's tortoise and hare.
+pub fn floyd<S, F>(initial: &S, mut advance: F) -> Cycle
+where
+    S: Clone + PartialEq,
+    F: FnMut(&mut S),
+{
+    // the hare goes twice as fast, until they meet in the loop, at a step
+    // multiple of its length
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    loop {
+        advance(&mut tortoise);
+        advance(&mut hare);
+        advance(&mut hare);
+        if tortoise == hare {
+            break;
+        }
+    }
+
+    // both at the same pace, one from the start, meet at the loop start
+    let mut start = 0;
+    let mut tortoise = initial.clone();
+    while tortoise != hare {
+        advance(&mut tortoise);
+        advance(&mut hare);
+        start += 1;
+    }
+
+    let mut len = 1;
+    advance(&mut hare);
+    while tortoise != hare {
+        advance(&mut hare);
+        len += 1;
+    }
+    Cycle { start, len }
+}
+
+/// Brent's algorithm, advancing the sequence less than Floyd's.
+pub fn brent<S, F>(initial: &S, mut advance: F) -> Cycle
+where
+    S: Clone + PartialEq,
+    F: FnMut(&mut S),
+{
+    // the tortoise teleports to the hare at every power of two, until
+    // the hare comes back to it, which gives the length
+    let mut power = 1;
+    let mut len = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    advance(&mut hare);
+    while tortoise != hare {
+        if power == len {
+            tortoise = hare.clone();
+            power *= 2;
+            len = 0;
+        }
+        advance(&mut hare);
+        len += 1;
+    }
+
+    // with the hare `len` steps ahead, both meet at the loop start
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    for _ in 0..len {
+        advance(&mut hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        advance(&mut tortoise);
+        advance(&mut hare);
+        start += 1;
+    }
+    Cycle { start, len }
+}
+
+/// Remembers the step of every state, until one comes back.
+pub fn hashing<S, F>(initial: &S, mut advance: F) -> Cycle
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&mut S),
+{
+    let mut seen = HashMap::new();
+    let mut state = initial.clone();
+    let mut step = 0;
+    loop {
+        if let Some(start) = seen.insert(state.clone(), step) {
+            return Cycle {
+                start,
+                len: step - start,
+            };
+        }
+        advance(&mut state);
+        step += 1;
+    }
+}
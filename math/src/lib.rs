@@ -0,0 +1,256 @@
+//! Number theory for the puzzles about cycles and periods.
+//!
+//! Every function is generic over the primitive integers through the
+//! [`Integer`] trait, and never overflows on intermediate results: when
+//! the result itself does not fit, `None` is returned.
+//!
+//! The [`cycle`] module finds the loops of sequences of states.
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+pub mod cycle;
+
+/// The primitive integer types.
+pub trait Integer:
+    Copy
+    + Ord
+    + fmt::Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_mul(self, other: Self) -> Option<Self>;
+
+    /// Remainder, none when dividing by zero or `MIN` by `-1`.
+    fn checked_rem(self, other: Self) -> Option<Self>;
+
+    /// Absolute value, none if it does not fit.
+    fn checked_abs(self) -> Option<Self>;
+}
+
+/// The signed primitive integer types.
+pub trait Signed: Integer + Neg<Output = Self> {}
+
+macro_rules! unsigned {
+    ($($t:ty)*) => {$(
+        impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$t>::checked_mul(self, other)
+            }
+
+            fn checked_rem(self, other: Self) -> Option<Self> {
+                <$t>::checked_rem(self, other)
+            }
+
+            fn checked_abs(self) -> Option<Self> {
+                Some(self)
+            }
+        }
+    )*};
+}
+
+macro_rules! signed {
+    ($($t:ty)*) => {$(
+        impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$t>::checked_mul(self, other)
+            }
+
+            fn checked_rem(self, other: Self) -> Option<Self> {
+                <$t>::checked_rem(self, other)
+            }
+
+            fn checked_abs(self) -> Option<Self> {
+                <$t>::checked_abs(self)
+            }
+        }
+
+        impl Signed for $t {}
+    )*};
+}
+
+unsigned!(u8 u16 u32 u64 u128 usize);
+signed!(i8 i16 i32 i64 i128 isize);
+
+/// Greatest common divisor, always positive but for `gcd(0, 0) == 0`.
+///
+/// Only panics for signed types when the result is `MIN.abs()`, as in
+/// `gcd(i32::MIN, 0)`.
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    euclid(a, b)
+        .checked_abs()
+        .expect("gcd does not fit in the type")
+}
+
+/// Greatest common divisor, up to its sign.
+fn euclid<T: Integer>(mut a: T, mut b: T) -> T {
+    while b != T::ZERO {
+        // MIN % -1 overflows, while being 0
+        let r = a.checked_rem(b).unwrap_or(T::ZERO);
+        a = b;
+        b = r;
+    }
+    a
+}
+
+/// Least common multiple, positive but for `lcm(x, 0) == 0`, none if it
+/// does not fit.
+pub fn lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    // a gcd of MIN.abs() does not fit, and neither does the lcm, which
+    // it divides
+    let g = euclid(a, b).checked_abs()?;
+    // dividing first avoids overflowing on a * b
+    (a / g).checked_mul(b)?.checked_abs()
+}
+
+/// Least common multiple of all the values, none if it does not fit.
+pub fn lcm_all<T, I>(values: I) -> Option<T>
+where
+    T: Integer,
+    I: IntoIterator<Item = T>,
+{
+    values.into_iter().try_fold(T::ONE, |acc, v| lcm(acc, v))
+}
+
+/// Extended Euclid: returns `(g, x, y)` such that `a * x + b * y == g`,
+/// `g` being the non negative gcd of `a` and `b`.
+pub fn extended_gcd<T: Signed>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::ONE, T::ZERO);
+    let (mut old_y, mut y) = (T::ZERO, T::ONE);
+
+    while r != T::ZERO {
+        let q = old_r / r;
+        let next = old_r - q * r;
+        old_r = r;
+        r = next;
+        let next = old_x - q * x;
+        old_x = x;
+        x = next;
+        let next = old_y - q * y;
+        old_y = y;
+        y = next;
+    }
+
+    if old_r < T::ZERO {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// `a` modulo `m`, in `0..m`. `m` must be positive.
+pub fn modulo<T: Integer>(a: T, m: T) -> T {
+    assert!(m > T::ZERO, "modulus must be positive");
+    let r = a % m;
+    if r < T::ZERO {
+        r + m
+    } else {
+        r
+    }
+}
+
+/// `(a + b) % m`, for `a` and `b` in `0..m`.
+fn add_mod<T: Integer>(a: T, b: T, m: T) -> T {
+    // a + b could overflow, but m - b cannot
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+/// `(a * b) % m`, without overflowing whatever the modulus.
+pub fn mul_mod<T: Integer>(a: T, b: T, m: T) -> T {
+    let (mut a, mut b) = (modulo(a, m), modulo(b, m));
+    if let Some(p) = a.checked_mul(b) {
+        return p % m;
+    }
+
+    // double and add, b being halved at each step
+    let two = T::ONE + T::ONE;
+    let mut result = T::ZERO;
+    while b > T::ZERO {
+        if b % two == T::ONE {
+            result = add_mod(result, a, m);
+        }
+        a = add_mod(a, a, m);
+        b = b / two;
+    }
+    result
+}
+
+/// `base` to the power of `exp`, modulo `m`. `exp` must not be negative.
+pub fn mod_pow<T: Integer>(base: T, mut exp: T, m: T) -> T {
+    assert!(exp >= T::ZERO, "exponent must not be negative");
+    let two = T::ONE + T::ONE;
+    let mut base = modulo(base, m);
+    let mut result = modulo(T::ONE, m);
+    while exp > T::ZERO {
+        if exp % two == T::ONE {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp = exp / two;
+    }
+    result
+}
+
+/// Inverse of `a` modulo `m`, in `0..m`, none if they are not coprime.
+pub fn mod_inverse<T: Signed>(a: T, m: T) -> Option<T> {
+    let (g, x, _) = extended_gcd(modulo(a, m), m);
+    if g == T::ONE {
+        Some(modulo(x, m))
+    } else {
+        None
+    }
+}
+
+/// Chinese remainder theorem: the smallest non negative `x` such that
+/// `x % m == r` for every `(r, m)` of the congruences, along with the
+/// lcm of the moduli, which is the period of the solutions.
+///
+/// The moduli do not need to be coprime. Returns none if the
+/// congruences have no common solution, or if the lcm does not fit.
+pub fn crt<T, I>(congruences: I) -> Option<(T, T)>
+where
+    T: Signed,
+    I: IntoIterator<Item = (T, T)>,
+{
+    let mut x = T::ZERO;
+    let mut period = T::ONE;
+
+    for (r, m) in congruences {
+        // solve x + period * k == r (mod m)
+        let (g, p, _) = extended_gcd(period, m);
+        let diff = modulo(r, m) - modulo(x, m);
+        if diff % g != T::ZERO {
+            return None;
+        }
+
+        let m_g = m / g;
+        let k = mul_mod(modulo(diff / g, m_g), modulo(p, m_g), m_g);
+        let next_period = (period / g).checked_mul(m)?;
+        x = add_mod(
+            modulo(x, next_period),
+            mul_mod(period, k, next_period),
+            next_period,
+        );
+        period = next_period;
+    }
+    Some((x, period))
+}
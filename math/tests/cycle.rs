@@ -0,0 +1,92 @@
+use math::cycle::{self, Cycle};
+
+/// x -> (x * x + c) % m, whose sequences loop with all kinds of starts
+/// and lengths.
+fn advance(c: u64, m: u64) -> impl Fn(&mut u64) + Copy {
+    move |x| *x = (*x * *x + c) % m
+}
+
+/// The cycle found by keeping the whole sequence.
+fn naive(initial: u64, c: u64, m: u64) -> Cycle {
+    let mut states = vec![initial];
+    loop {
+        let mut next = *states.last().unwrap();
+        advance(c, m)(&mut next);
+        if let Some(start) = states.iter().position(|s| *s == next) {
+            return Cycle {
+                start: start as u64,
+                len: (states.len() - start) as u64,
+            };
+        }
+        states.push(next);
+    }
+}
+
+#[test]
+fn detect() {
+    for m in 1..60 {
+        for c in 0..5 {
+            for x in 0..m {
+                let expected = naive(x, c, m);
+                assert_eq!(cycle::floyd(&x, advance(c, m)), expected, "floyd");
+                assert_eq!(cycle::brent(&x, advance(c, m)), expected, "brent");
+                assert_eq!(cycle::hashing(&x, advance(c, m)), expected, "hashing");
+            }
+        }
+    }
+
+    // a pure loop, as reversible systems make
+    let rotate = |v: &mut Vec<u8>| v.rotate_left(1);
+    let expected = Cycle { start: 0, len: 5 };
+    assert_eq!(cycle::brent(&vec![1, 2, 3, 4, 5], rotate), expected);
+    assert_eq!(cycle::floyd(&vec![1, 2, 3, 4, 5], rotate), expected);
+    assert_eq!(cycle::hashing(&vec![1, 2, 3, 4, 5], rotate), expected);
+    // a fixed point
+    let expected = Cycle { start: 0, len: 1 };
+    assert_eq!(cycle::brent(&7, |_: &mut u8| ()), expected);
+}
+
+#[test]
+fn extrapolate() {
+    let (c, m) = (3, 1009);
+    let cycle = cycle::brent(&2, advance(c, m));
+    assert!(cycle.start > 0 && cycle.len > 1);
+
+    let mut state = 2;
+    for n in 0..3 * (cycle.start + cycle.len) {
+        assert_eq!(cycle.state_at(&2, advance(c, m), n), state, "step {}", n);
+        assert!(cycle.reduce(n) < cycle.start + cycle.len);
+        advance(c, m)(&mut state);
+    }
+
+    let far = 1_000_000_000_000;
+    let reduced = cycle.reduce(far);
+    assert_eq!(reduced, cycle.reduce(far + cycle.len));
+    assert_eq!(
+        cycle.state_at(&2, advance(c, m), far),
+        cycle.state_at(&2, advance(c, m), reduced)
+    );
+}
+
+#[test]
+fn combine() {
+    let (a, b) = ((3, 1009), (1, 97));
+    let cycles = [
+        cycle::brent(&2, advance(a.0, a.1)),
+        cycle::brent(&5, advance(b.0, b.1)),
+    ];
+    let expected = cycle::hashing(&(2, 5), |(x, y): &mut (u64, u64)| {
+        advance(a.0, a.1)(x);
+        advance(b.0, b.1)(y);
+    });
+    assert_eq!(cycle::product(cycles.iter().copied()), Some(expected));
+    assert_eq!(expected.first_repeat(), expected.start + expected.len);
+
+    assert_eq!(cycle::product(None), Some(Cycle { start: 0, len: 1 }));
+    let huge = Cycle {
+        start: 0,
+        len: u64::MAX,
+    };
+    let other = Cycle { start: 0, len: 2 };
+    assert_eq!(cycle::product(vec![huge, other]), None);
+}
@@ -0,0 +1,104 @@
+use math::{crt, extended_gcd, gcd, lcm, lcm_all, mod_inverse, mod_pow, modulo, mul_mod};
+
+#[test]
+fn gcd_and_lcm() {
+    assert_eq!(gcd(12u32, 18), 6);
+    assert_eq!(gcd(-12i64, 18), 6);
+    assert_eq!(gcd(12i8, -18), 6);
+    assert_eq!(gcd(0u8, 7), 7);
+    assert_eq!(gcd(-7i16, 0), 7);
+    assert_eq!(gcd(0usize, 0), 0);
+    assert_eq!(gcd(i32::MIN, -1), 1);
+    assert_eq!(gcd(i32::MIN, 6), 2);
+
+    assert_eq!(lcm(4u64, 6), Some(12));
+    assert_eq!(lcm(-4i32, 6), Some(12));
+    assert_eq!(lcm(0u8, 5), Some(0));
+    // a * b would overflow, while the lcm fits
+    assert_eq!(lcm(1u64 << 40, 3 << 40), Some(3 << 40));
+    assert_eq!(lcm(200u8, 150), None);
+    assert_eq!(lcm(i8::MIN, 1), None);
+    assert_eq!(lcm(i32::MIN, i32::MIN), None);
+    assert_eq!(lcm(i32::MIN, -1), None);
+    assert_eq!(lcm(i32::MIN / 2, i32::MIN), None);
+    assert_eq!(lcm(i32::MIN / 2, 2), Some(1 << 30));
+
+    assert_eq!(lcm_all(vec![18u64, 28, 44]), Some(2772));
+    assert_eq!(lcm_all(Vec::<u32>::new()), Some(1));
+    assert_eq!(lcm_all(vec![u32::MAX, u32::MAX - 1]), None);
+}
+
+#[test]
+fn extended_euclid() {
+    for a in -30i32..30 {
+        for b in -30i32..30 {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b), "gcd({}, {})", a, b);
+            assert_eq!(a * x + b * y, g, "bezout({}, {})", a, b);
+        }
+    }
+}
+
+#[test]
+fn modular_arithmetic() {
+    assert_eq!(modulo(-7i32, 3), 2);
+    assert_eq!(modulo(7u8, 3), 1);
+
+    assert_eq!(mul_mod(u64::MAX - 1, u64::MAX - 2, u64::MAX), 2);
+    assert_eq!(mul_mod(i128::MAX - 1, i128::MAX - 1, i128::MAX), 1);
+    assert_eq!(mul_mod(-3i64, 5, 7), 6);
+
+    assert_eq!(mod_pow(2u64, 10, 1000), 24);
+    assert_eq!(mod_pow(3i64, 0, 7), 1);
+    assert_eq!(mod_pow(3i64, 0, 1), 0);
+    assert_eq!(mod_pow(-2i32, 3, 5), 2);
+    // Fermat's little theorem, with a modulus whose squares overflow
+    let p = 18_446_744_073_709_551_557u64;
+    assert_eq!(mod_pow(123_456_789u64, p - 1, p), 1);
+
+    for m in 2i32..40 {
+        for a in -40i32..40 {
+            match mod_inverse(a, m) {
+                Some(inv) => {
+                    assert!(inv >= 0 && inv < m);
+                    assert_eq!(modulo(a * inv, m), 1, "{}^-1 mod {}", a, m);
+                }
+                None => assert_ne!(gcd(a, m), 1, "{} mod {}", a, m),
+            }
+        }
+    }
+}
+
+#[test]
+fn chinese_remainder() {
+    assert_eq!(crt(vec![(2i64, 3), (3, 5), (2, 7)]), Some((23, 105)));
+    assert_eq!(crt(vec![(-1i64, 3), (8, 5)]), Some((8, 15)));
+    // moduli sharing factors
+    assert_eq!(crt(vec![(3i32, 4), (5, 6)]), Some((11, 12)));
+    assert_eq!(crt(vec![(1i32, 4), (2, 6)]), None);
+    assert_eq!(crt(Vec::<(i32, i32)>::new()), Some((0, 1)));
+
+    // brute force on small moduli
+    for m1 in 1i32..12 {
+        for m2 in 1i32..12 {
+            for r1 in 0..m1 {
+                for r2 in 0..m2 {
+                    let period = lcm(m1, m2).unwrap();
+                    let expected = (0..period).find(|x| x % m1 == r1 && x % m2 == r2);
+                    let result = crt(vec![(r1, m1), (r2, m2)]);
+                    assert_eq!(result, expected.map(|x| (x, period)));
+                }
+            }
+        }
+    }
+
+    assert_eq!(crt(vec![(1i8, 11), (2, 11)]), None);
+
+    // the period does not fit
+    assert_eq!(crt(vec![(0i8, 11), (0, 13)]), None);
+    // while the product of the moduli would not fit either
+    assert_eq!(
+        crt(vec![(3i64, 1 << 40), ((1 << 20) + 3, 3 << 20)]),
+        Some(((1 << 40) + 3, 3 << 40))
+    );
+}
@@ -0,0 +1,109 @@
+//! Recognition of the block letters drawn by some puzzles.
+//!
+//! Letters are matched against the font of their height, 6 pixels for
+//! the small one and 10 for the large one, from left to right. They are
+//! usually separated by blank columns, but wide letters such as the
+//! small `Y` can touch the next one.
+use grid::{Grid, Pos};
+use std::cmp::Reverse;
+use std::fmt;
+
+mod fonts;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// No font has the height of the lit pixels.
+    Height(usize),
+    /// Some glyphs are not letters of the font. They are replaced by `?`
+    /// in `text`, and `columns` holds the first column of each.
+    Unrecognized { text: String, columns: Vec<usize> },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Height(height) => write!(f, "no font is {} pixels high", height),
+            Error::Unrecognized { text, columns } => {
+                let columns: Vec<_> = columns.iter().map(|c| c.to_string()).collect();
+                write!(
+                    f,
+                    "unrecognized glyphs at columns {} in {:?}",
+                    columns.join(", "),
+                    text
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Reads the letters of an image, `true` pixels being lit.
+pub fn recognize(image: &Grid<bool>) -> Result<String, Error> {
+    let (width, height) = (image.width() as i64, image.height() as i64);
+    let lit_row = |y| (0..width).any(|x| image[Pos::new(x, y)]);
+
+    let top = match (0..height).find(|y| lit_row(*y)) {
+        Some(top) => top,
+        None => return Ok(String::new()),
+    };
+    let bottom = (0..height).rev().find(|y| lit_row(*y)).unwrap_or(top);
+    let font_height = (bottom - top + 1) as usize;
+    let font = fonts::ALL
+        .iter()
+        .find(|font| font.height == font_height)
+        .ok_or(Error::Height(font_height))?;
+
+    // wider letters first, so that none is read as the start of another
+    let mut glyphs: Vec<_> = font.glyphs.iter().collect();
+    glyphs.sort_by_key(|(_, rows)| Reverse(rows[0].len()));
+
+    let lit_column = |x| (top..=bottom).any(|y| image[Pos::new(x, y)]);
+    let mut text = String::new();
+    let mut columns = Vec::new();
+    let mut x = 0;
+    while x < width {
+        if !lit_column(x) {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        let letter = glyphs.iter().find(|(_, rows)| {
+            start + rows[0].len() as i64 <= width
+                && matches(rows, |gx, gy| {
+                    image[Pos::new(start + gx as i64, top + gy as i64)]
+                })
+        });
+        match letter {
+            Some((letter, rows)) => {
+                text.push(*letter);
+                x += rows[0].len() as i64;
+            }
+            None => {
+                text.push('?');
+                columns.push(start as usize);
+                while x < width && lit_column(x) {
+                    x += 1;
+                }
+            }
+        }
+    }
+
+    if columns.is_empty() {
+        Ok(text)
+    } else {
+        Err(Error::Unrecognized { text, columns })
+    }
+}
+
+fn matches<F>(rows: &[&str], lit: F) -> bool
+where
+    F: Fn(usize, usize) -> bool,
+{
+    rows.iter().enumerate().all(|(y, row)| {
+        row.chars()
+            .enumerate()
+            .all(|(x, c)| (c == '#') == lit(x, y))
+    })
+}
@@ -0,0 +1,85 @@
+use grid::Grid;
+use ocr::Error;
+
+fn image(art: &str) -> Grid<bool> {
+    Grid::parse(art, |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
+    .unwrap()
+}
+
+#[test]
+fn small_letters() {
+    let art = "\
+........................
+.##..#..#..##..#..#.####
+#..#.#.#..#..#.#.#..#...
+#....##...#....##...###.
+#.##.#.#..#....#.#..#...
+#..#.#.#..#..#.#.#..#...
+.###.#..#..##..#..#.####
+";
+    assert_eq!(ocr::recognize(&image(art)).unwrap(), "GKCKE");
+}
+
+#[test]
+fn touching_letters() {
+    // the small `Y` fills its 5 pixel pitch, touching the next letter
+    let art = "\
+#..#.#...#.##.
+#.#..#...##..#
+##....#.#.#..#
+#.#....#..####
+#.#....#..#..#
+#..#...#..#..#
+";
+    assert_eq!(ocr::recognize(&image(art)).unwrap(), "KYA");
+}
+
+#[test]
+fn large_letters() {
+    let art = "\
+#....#..######...#####.
+#....#..#........#....#
+.#..#...#........#....#
+.#..#...#........#....#
+..##....#####....#####.
+..##....#........#..#..
+.#..#...#........#...#.
+.#..#...#........#...#.
+#....#..#........#....#
+#....#..######...#....#
+";
+    assert_eq!(ocr::recognize(&image(art)).unwrap(), "XER");
+}
+
+#[test]
+fn unrecognized_glyphs() {
+    let art = "\
+.##..#...####
+#..#.#...#..#
+#..#.#...#..#
+####.#...#..#
+#..#.##..#..#
+#..#.#...####
+";
+    let err = ocr::recognize(&image(art)).unwrap_err();
+    assert_eq!(
+        err,
+        Error::Unrecognized {
+            text: "A??".to_owned(),
+            columns: vec![5, 9],
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "unrecognized glyphs at columns 5, 9 in \"A??\""
+    );
+
+    let err = ocr::recognize(&image("#.#\n###\n#.#\n")).unwrap_err();
+    assert_eq!(err, Error::Height(3));
+
+    assert_eq!(ocr::recognize(&image("...\n...\n")).unwrap(), "");
+}
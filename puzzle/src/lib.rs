@@ -0,0 +1,282 @@
+//! Parsers for the input formats of the puzzles.
+//!
+//! Every parser reports the line and column of the first invalid token,
+//! both starting at 1.
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// A line of the input, used to locate its tokens.
+#[derive(Clone, Copy)]
+struct Line<'a> {
+    text: &'a str,
+    number: usize,
+}
+
+impl<'a> Line<'a> {
+    /// Error located at the start of `token`, which must be a slice of
+    /// the line.
+    fn error<S: Into<String>>(&self, token: &str, message: S) -> Error {
+        let offset = token.as_ptr() as usize - self.text.as_ptr() as usize;
+        Error {
+            line: self.number,
+            column: self.text[..offset].chars().count() + 1,
+            message: message.into(),
+        }
+    }
+
+    fn parse<T>(&self, token: &'a str, what: &str) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let token = token.trim();
+        if token.is_empty() {
+            return Err(self.error(token, format!("expected {}", what)));
+        }
+        token
+            .parse()
+            .map_err(|e| self.error(token, format!("invalid {} {:?}: {}", what, token, e)))
+    }
+
+    /// Splits the line in two around the first `sep`.
+    fn split_once(&self, sep: &str) -> Result<(&'a str, &'a str)> {
+        self.text.split_once(sep).ok_or_else(|| {
+            let end = &self.text[self.text.len()..];
+            self.error(end, format!("expected `{}`", sep))
+        })
+    }
+}
+
+/// The non-empty lines of the input.
+fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, text)| Line {
+            text,
+            number: idx + 1,
+        })
+        .filter(|line| !line.text.trim().is_empty())
+}
+
+/// Comma separated values, such as an Intcode program.
+///
+/// Values can span several lines, and at least one is required.
+pub fn comma_separated<T>(input: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let mut values = Vec::new();
+    for line in lines(input) {
+        for token in line.text.split(',') {
+            values.push(line.parse(token, "value")?);
+        }
+    }
+
+    if values.is_empty() {
+        return Err(Error {
+            line: 1,
+            column: 1,
+            message: "expected a value".to_owned(),
+        });
+    }
+    Ok(values)
+}
+
+/// One value per line.
+pub fn one_per_line<T>(input: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    lines(input)
+        .map(|line| line.parse(line.text, "value"))
+        .collect()
+}
+
+/// A single line of decimal digits.
+pub fn digits(input: &str) -> Result<Vec<u32>> {
+    let mut lines = lines(input);
+    let line = match lines.next() {
+        Some(line) => line,
+        None => return Ok(Vec::new()),
+    };
+    if let Some(extra) = lines.next() {
+        return Err(extra.error(extra.text, "expected a single line of digits"));
+    }
+
+    let text = line.text.trim();
+    text.char_indices()
+        .map(|(idx, c)| {
+            c.to_digit(10)
+                .ok_or_else(|| line.error(&text[idx..], format!("invalid digit {:?}", c)))
+        })
+        .collect()
+}
+
+/// Pairs of names separated by `sep` on each line, such as `COM)B`.
+pub fn pairs<'a>(input: &'a str, sep: &str) -> Result<Vec<(&'a str, &'a str)>> {
+    lines(input)
+        .map(|line| {
+            let (a, b) = line.split_once(sep)?;
+            let (a, b) = (a.trim(), b.trim());
+            if a.is_empty() {
+                return Err(line.error(a, "expected a name"));
+            }
+            if b.is_empty() {
+                return Err(line.error(b, "expected a name"));
+            }
+            Ok((a, b))
+        })
+        .collect()
+}
+
+/// One vector per line, written as `<x=-1, y=0, z=2>`.
+///
+/// The names of the components are not checked, but all vectors must
+/// have the same number of them.
+pub fn vectors(input: &str) -> Result<Vec<Vec<i64>>> {
+    let mut vectors: Vec<Vec<i64>> = Vec::new();
+
+    for line in lines(input) {
+        let text = line.text.trim();
+        let inner = text
+            .strip_prefix('<')
+            .ok_or_else(|| line.error(text, "expected `<`"))?;
+        let inner = inner
+            .strip_suffix('>')
+            .ok_or_else(|| line.error(&text[text.len()..], "expected `>`"))?;
+
+        let mut vector = Vec::new();
+        for component in inner.split(',') {
+            let (name, value) = component
+                .split_once('=')
+                .ok_or_else(|| line.error(component.trim_start(), "expected `name=value`"))?;
+            if name.trim().is_empty() {
+                return Err(line.error(component.trim_start(), "expected a component name"));
+            }
+            vector.push(line.parse(value, "coordinate")?);
+        }
+
+        if let Some(first) = vectors.first() {
+            if first.len() != vector.len() {
+                return Err(line.error(
+                    text,
+                    format!(
+                        "expected {} components, found {}",
+                        first.len(),
+                        vector.len()
+                    ),
+                ));
+            }
+        }
+        vectors.push(vector);
+    }
+    Ok(vectors)
+}
+
+/// An amount of a chemical, such as `7 ORE`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quantity<'a> {
+    pub count: u64,
+    pub chemical: &'a str,
+}
+
+/// A reaction such as `7 A, 1 B => 1 C`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reaction<'a> {
+    pub inputs: Vec<Quantity<'a>>,
+    pub output: Quantity<'a>,
+}
+
+impl<'a> Line<'a> {
+    fn quantity(&self, token: &'a str) -> Result<Quantity<'a>> {
+        let token = token.trim();
+        let (count, chemical) = token
+            .split_once(' ')
+            .ok_or_else(|| self.error(token, "expected a count and a chemical"))?;
+        let chemical = chemical.trim();
+        if chemical.is_empty() || !chemical.chars().all(char::is_alphanumeric) {
+            return Err(self.error(chemical, format!("invalid chemical {:?}", chemical)));
+        }
+
+        Ok(Quantity {
+            count: self.parse(count, "count")?,
+            chemical,
+        })
+    }
+}
+
+/// One reaction per line.
+pub fn reactions(input: &str) -> Result<Vec<Reaction<'_>>> {
+    lines(input)
+        .map(|line| {
+            let (inputs, output) = line.split_once("=>")?;
+            Ok(Reaction {
+                inputs: inputs
+                    .split(',')
+                    .map(|token| line.quantity(token))
+                    .collect::<Result<_>>()?,
+                output: line.quantity(output)?,
+            })
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// A straight part of a wire, such as `R75`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Segment {
+    pub direction: Direction,
+    pub length: u32,
+}
+
+/// One wire per line, made of comma separated segments.
+pub fn wires(input: &str) -> Result<Vec<Vec<Segment>>> {
+    lines(input)
+        .map(|line| {
+            line.text
+                .split(',')
+                .map(|token| {
+                    let token = token.trim();
+                    let direction = match token.chars().next() {
+                        Some('U') => Direction::Up,
+                        Some('D') => Direction::Down,
+                        Some('L') => Direction::Left,
+                        Some('R') => Direction::Right,
+                        _ => return Err(line.error(token, format!("invalid segment {:?}", token))),
+                    };
+                    Ok(Segment {
+                        direction,
+                        length: line.parse(&token[1..], "length")?,
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
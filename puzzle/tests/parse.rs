@@ -0,0 +1,125 @@
+use puzzle::{Direction, Quantity, Segment};
+
+#[test]
+fn comma_separated() {
+    let values: Vec<i64> = puzzle::comma_separated("1,-2, 3\n4,5\n").unwrap();
+    assert_eq!(values, [1, -2, 3, 4, 5]);
+
+    let err = puzzle::comma_separated::<i64>("1,2\n3,x4\n").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 3));
+    assert!(err.message.contains("\"x4\""), "{}", err);
+
+    let err = puzzle::comma_separated::<i64>("1,,2").unwrap_err();
+    assert_eq!((err.line, err.column), (1, 3));
+
+    let err = puzzle::comma_separated::<i64>("\n").unwrap_err();
+    assert_eq!(err.to_string(), "1:1: expected a value");
+}
+
+#[test]
+fn one_per_line() {
+    let values: Vec<u32> = puzzle::one_per_line("12\n 14\n\n1969\n").unwrap();
+    assert_eq!(values, [12, 14, 1969]);
+
+    let err = puzzle::one_per_line::<u32>("12\n  -14\n").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 3));
+    assert!(puzzle::one_per_line::<u32>("").unwrap().is_empty());
+}
+
+#[test]
+fn digits() {
+    assert_eq!(puzzle::digits("0123\n").unwrap(), [0, 1, 2, 3]);
+
+    let err = puzzle::digits("01é3").unwrap_err();
+    assert_eq!((err.line, err.column), (1, 3));
+
+    let err = puzzle::digits("012\n345\n").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 1));
+}
+
+#[test]
+fn pairs() {
+    let pairs = puzzle::pairs("COM)B\nB)C\n", ")").unwrap();
+    assert_eq!(pairs, [("COM", "B"), ("B", "C")]);
+
+    let err = puzzle::pairs("COM)B\nBC\n", ")").unwrap_err();
+    assert_eq!(err.to_string(), "2:3: expected `)`");
+
+    let err = puzzle::pairs("COM)\n", ")").unwrap_err();
+    assert_eq!((err.line, err.column), (1, 5));
+}
+
+#[test]
+fn vectors() {
+    let vectors = puzzle::vectors("<x=-1, y=0, z=2>\n<x=2, y=-10, z=-7>\n").unwrap();
+    assert_eq!(vectors, [vec![-1, 0, 2], vec![2, -10, -7]]);
+
+    let vectors = puzzle::vectors("<a=1, b=2, c=3, d=4>\n").unwrap();
+    assert_eq!(vectors, [vec![1, 2, 3, 4]]);
+
+    let err = puzzle::vectors("<x=1, y=z, z=3>").unwrap_err();
+    assert_eq!((err.line, err.column), (1, 9));
+
+    let err = puzzle::vectors("<x=1, y=2, z=3>\n<x=1, y=2>\n").unwrap_err();
+    assert_eq!(err.to_string(), "2:1: expected 3 components, found 2");
+
+    let err = puzzle::vectors("<x=1, y=2, z=3").unwrap_err();
+    assert_eq!((err.line, err.column), (1, 15));
+}
+
+#[test]
+fn reactions() {
+    let reactions = puzzle::reactions("10 ORE => 10 A\n7 A, 1 B => 1 FUEL\n").unwrap();
+    assert_eq!(reactions.len(), 2);
+    assert_eq!(
+        reactions[1].inputs,
+        [
+            Quantity {
+                count: 7,
+                chemical: "A"
+            },
+            Quantity {
+                count: 1,
+                chemical: "B"
+            }
+        ]
+    );
+    assert_eq!(
+        reactions[1].output,
+        Quantity {
+            count: 1,
+            chemical: "FUEL"
+        }
+    );
+
+    let err = puzzle::reactions("10 ORE => 10 A\n7 A, x B => 1 FUEL\n").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 6));
+
+    let err = puzzle::reactions("10 ORE -> 10 A\n").unwrap_err();
+    assert_eq!(err.to_string(), "1:15: expected `=>`");
+}
+
+#[test]
+fn wires() {
+    let wires = puzzle::wires("R8,U5\nU7,L6\n").unwrap();
+    assert_eq!(
+        wires[0],
+        [
+            Segment {
+                direction: Direction::Right,
+                length: 8
+            },
+            Segment {
+                direction: Direction::Up,
+                length: 5
+            }
+        ]
+    );
+    assert_eq!(wires[1][1].direction, Direction::Left);
+
+    let err = puzzle::wires("R8,U5\nU7,X6\n").unwrap_err();
+    assert_eq!(err.to_string(), "2:4: invalid segment \"X6\"");
+
+    let err = puzzle::wires("R8,U\n").unwrap_err();
+    assert_eq!((err.line, err.column), (1, 5));
+}
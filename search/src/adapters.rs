@@ -0,0 +1,32 @@
+use grid::{Grid, GrowingGrid, Pos};
+
+/// Neighbor function over the orthogonally adjacent tiles of a grid that
+/// are `passable`.
+pub fn grid_neighbors<'a, T, P>(grid: &'a Grid<T>, passable: P) -> impl FnMut(&Pos) -> Vec<Pos> + 'a
+where
+    P: Fn(&T) -> bool + 'a,
+{
+    move |pos| {
+        grid.neighbors4(*pos)
+            .filter(|n| passable(&grid[*n]))
+            .collect()
+    }
+}
+
+/// Neighbor function over the orthogonally adjacent tiles of a growing
+/// grid that are `passable`.
+pub fn growing_grid_neighbors<'a, T, P>(
+    grid: &'a GrowingGrid<T>,
+    passable: P,
+) -> impl FnMut(&Pos) -> Vec<Pos> + 'a
+where
+    P: Fn(&T) -> bool + 'a,
+{
+    move |pos| {
+        pos.neighbors4()
+            .iter()
+            .copied()
+            .filter(|n| passable(grid.get(*n)))
+            .collect()
+    }
+}
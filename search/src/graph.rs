@@ -0,0 +1,59 @@
+use std::collections::{HashMap, HashSet};
+
+/// A graph whose nodes are names, such as the orbit map.
+#[derive(Debug, Clone, Default)]
+pub struct Graph<'a> {
+    edges: HashMap<&'a str, Vec<&'a str>>,
+}
+
+impl<'a> Graph<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Graph with an edge from the first to the second name of every
+    /// pair.
+    pub fn directed<I>(pairs: I) -> Self
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        let mut graph = Self::new();
+        for (from, to) in pairs {
+            graph.add_edge(from, to);
+        }
+        graph
+    }
+
+    /// Graph with edges in both directions between the names of every
+    /// pair.
+    pub fn undirected<I>(pairs: I) -> Self
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        let mut graph = Self::new();
+        for (a, b) in pairs {
+            graph.add_edge(a, b);
+            graph.add_edge(b, a);
+        }
+        graph
+    }
+
+    pub fn add_edge(&mut self, from: &'a str, to: &'a str) {
+        self.edges.entry(from).or_default().push(to);
+        self.edges.entry(to).or_default();
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.edges.keys().copied()
+    }
+
+    pub fn neighbors(&self, node: &str) -> impl Iterator<Item = &'a str> + '_ {
+        self.edges.get(node).into_iter().flatten().copied()
+    }
+
+    /// Nodes without incoming edges.
+    pub fn roots(&self) -> Vec<&'a str> {
+        let targets: HashSet<_> = self.edges.values().flatten().collect();
+        self.nodes().filter(|n| !targets.contains(n)).collect()
+    }
+}
@@ -0,0 +1,184 @@
+//! Shortest path searches over graphs given by a neighbor function.
+//!
+//! Nodes can be any hashable value. The graph is never built: the searches
+//! call the neighbor function on the nodes as they reach them, which makes
+//! it possible to explore a graph while searching it.
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+mod adapters;
+mod graph;
+
+pub use adapters::{grid_neighbors, growing_grid_neighbors};
+pub use graph::Graph;
+
+/// Result of a search: the distance of every reached node from the
+/// closest start, and its parent on a shortest path.
+#[derive(Debug, Clone)]
+pub struct Search<N> {
+    distances: HashMap<N, u64>,
+    parents: HashMap<N, N>,
+}
+
+impl<N: Eq + Hash + Clone> Search<N> {
+    fn new() -> Self {
+        Self {
+            distances: HashMap::new(),
+            parents: HashMap::new(),
+        }
+    }
+
+    /// Distance of `node` from the closest start, if it was reached.
+    pub fn distance(&self, node: &N) -> Option<u64> {
+        self.distances.get(node).copied()
+    }
+
+    /// Distances of all the reached nodes.
+    pub fn distances(&self) -> &HashMap<N, u64> {
+        &self.distances
+    }
+
+    /// Previous node on a shortest path to `node`, none for the starts.
+    pub fn parent(&self, node: &N) -> Option<&N> {
+        self.parents.get(node)
+    }
+
+    /// The shortest path from a start to `goal`, both included.
+    pub fn path(&self, goal: &N) -> Option<Vec<N>> {
+        if !self.distances.contains_key(goal) {
+            return None;
+        }
+
+        let mut path = vec![goal.clone()];
+        let mut node = goal;
+        while let Some(parent) = self.parents.get(node) {
+            path.push(parent.clone());
+            node = parent;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// A node furthest from the starts, with its distance.
+    pub fn furthest(&self) -> Option<(&N, u64)> {
+        self.distances
+            .iter()
+            .max_by_key(|(_, d)| **d)
+            .map(|(n, d)| (n, *d))
+    }
+}
+
+/// Breadth-first search, every edge having a length of 1.
+pub fn bfs<N, S, F, I>(starts: S, mut neighbors: F) -> Search<N>
+where
+    N: Eq + Hash + Clone,
+    S: IntoIterator<Item = N>,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    let mut search = Search::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if search.distances.insert(start.clone(), 0).is_none() {
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(node) = queue.pop_front() {
+        let distance = search.distances[&node] + 1;
+        for next in neighbors(&node) {
+            if let Entry::Vacant(e) = search.distances.entry(next.clone()) {
+                e.insert(distance);
+                search.parents.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+    search
+}
+
+/// Dijkstra's algorithm, `neighbors` returning the nodes along with the
+/// length of the edge leading to them.
+pub fn dijkstra<N, S, F, I>(starts: S, neighbors: F) -> Search<N>
+where
+    N: Eq + Hash + Clone,
+    S: IntoIterator<Item = N>,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    best_first(starts, None, neighbors, |_| 0)
+}
+
+/// A* search of a path to `goal`, stopping as soon as it is found.
+///
+/// `heuristic` estimates the distance from a node to the goal, and must
+/// never overestimate it for the path to be the shortest one.
+pub fn astar<N, F, I, H>(start: N, goal: &N, neighbors: F, heuristic: H) -> Search<N>
+where
+    N: Eq + Hash + Clone,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+    H: FnMut(&N) -> u64,
+{
+    best_first(Some(start), Some(goal), neighbors, heuristic)
+}
+
+fn best_first<N, S, F, I, H>(
+    starts: S,
+    goal: Option<&N>,
+    mut neighbors: F,
+    mut heuristic: H,
+) -> Search<N>
+where
+    N: Eq + Hash + Clone,
+    S: IntoIterator<Item = N>,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+    H: FnMut(&N) -> u64,
+{
+    let mut search = Search::new();
+    // nodes are kept aside so that the heap does not need them to be
+    // ordered
+    let mut nodes = Vec::new();
+    let mut heap = BinaryHeap::new();
+
+    for start in starts {
+        if search.distances.insert(start.clone(), 0).is_none() {
+            heap.push(Reverse((heuristic(&start), 0, nodes.len())));
+            nodes.push(start);
+        }
+    }
+
+    while let Some(Reverse((_, distance, idx))) = heap.pop() {
+        let node = nodes[idx].clone();
+        if search.distances[&node] < distance {
+            // already reached through a shorter path
+            continue;
+        }
+        if goal == Some(&node) {
+            break;
+        }
+
+        for (next, len) in neighbors(&node) {
+            let next_distance = distance + len;
+            let shorter = match search.distances.get(&next) {
+                Some(d) => next_distance < *d,
+                None => true,
+            };
+            if shorter {
+                search.distances.insert(next.clone(), next_distance);
+                search.parents.insert(next.clone(), node.clone());
+                heap.push(Reverse((
+                    next_distance + heuristic(&next),
+                    next_distance,
+                    nodes.len(),
+                )));
+                nodes.push(next);
+            }
+        }
+    }
+    search
+}
@@ -0,0 +1,120 @@
+use grid::{Grid, GrowingGrid, Pos};
+use search::Graph;
+
+const ORBITS: [(&str, &str); 13] = [
+    ("COM", "B"),
+    ("B", "C"),
+    ("C", "D"),
+    ("D", "E"),
+    ("E", "F"),
+    ("B", "G"),
+    ("G", "H"),
+    ("D", "I"),
+    ("E", "J"),
+    ("J", "K"),
+    ("K", "L"),
+    ("K", "YOU"),
+    ("I", "SAN"),
+];
+
+const MAZE: &str = "\
+.....
+.###.
+...#.
+##.#.
+.....
+";
+
+fn maze() -> Grid<bool> {
+    Grid::parse(MAZE, |c| match c {
+        '#' => Some(false),
+        '.' => Some(true),
+        _ => None,
+    })
+    .unwrap()
+}
+
+#[test]
+fn bfs_on_graph() {
+    let graph = Graph::directed(ORBITS.iter().copied());
+    assert_eq!(graph.roots(), vec!["COM"]);
+
+    let search = search::bfs(graph.roots(), |n| graph.neighbors(n));
+    assert_eq!(search.distances().len(), 14);
+    assert_eq!(search.distance(&"L"), Some(7));
+    assert_eq!(search.parent(&"L"), Some(&"K"));
+    assert_eq!(search.parent(&"COM"), None);
+
+    let graph = Graph::undirected(ORBITS.iter().copied());
+    let search = search::bfs(Some("YOU"), |n| graph.neighbors(n));
+    assert_eq!(
+        search.path(&"SAN").unwrap(),
+        vec!["YOU", "K", "J", "E", "D", "I", "SAN"]
+    );
+    assert_eq!(search.path(&"unknown"), None);
+}
+
+#[test]
+fn bfs_on_grid() {
+    let grid = maze();
+    let start = Pos::new(0, 0);
+    let search = search::bfs(Some(start), search::grid_neighbors(&grid, |t| *t));
+
+    let goal = Pos::new(0, 4);
+    assert_eq!(search.distance(&goal), Some(8));
+    let path = search.path(&goal).unwrap();
+    assert_eq!(path.len(), 9);
+    assert!(path.windows(2).all(|w| w[0].manhattan(w[1]) == 1));
+    assert!(path.iter().all(|p| grid[*p]));
+
+    // walls are never reached
+    assert_eq!(search.distance(&Pos::new(1, 1)), None);
+    let (_, furthest) = search.furthest().unwrap();
+    assert_eq!(furthest, 8);
+}
+
+#[test]
+fn bfs_on_growing_grid() {
+    let mut grid = GrowingGrid::new(false);
+    for x in -3..=3 {
+        grid.set(Pos::new(x, 0), true);
+    }
+
+    let search = search::bfs(
+        Some(Pos::new(0, 0)),
+        search::growing_grid_neighbors(&grid, |t| *t),
+    );
+    assert_eq!(search.distances().len(), 7);
+    assert_eq!(search.distance(&Pos::new(-3, 0)), Some(3));
+}
+
+#[test]
+fn dijkstra_and_astar() {
+    // a direct but expensive edge, and a longer cheaper route
+    let edges = |n: &u64| match n {
+        0 => vec![(3, 10), (1, 1)],
+        1 => vec![(2, 2)],
+        2 => vec![(3, 3)],
+        _ => vec![],
+    };
+
+    let search = search::dijkstra(Some(0), edges);
+    assert_eq!(search.distance(&3), Some(6));
+    assert_eq!(search.path(&3).unwrap(), vec![0, 1, 2, 3]);
+
+    let search = search::astar(0, &3, edges, |n| 3 - n);
+    assert_eq!(search.distance(&3), Some(6));
+    assert_eq!(search.path(&3).unwrap(), vec![0, 1, 2, 3]);
+
+    // on a grid, with the manhattan distance as heuristic
+    let grid = maze();
+    let (start, goal) = (Pos::new(0, 0), Pos::new(0, 4));
+    let mut neighbors = search::grid_neighbors(&grid, |t| *t);
+    let search = search::astar(
+        start,
+        &goal,
+        |p| neighbors(p).into_iter().map(|n| (n, 1)),
+        |p| p.manhattan(goal) as u64,
+    );
+    assert_eq!(search.distance(&goal), Some(8));
+}